@@ -0,0 +1,169 @@
+use anyhow::{bail, Context, Result};
+use chrono::{NaiveDate, Timelike};
+use ratatui::crossterm::{execute, terminal::SetTitle};
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    extract_tags,
+    resonance::{self, Reach},
+    App,
+};
+
+// --- Resonance Alerts ---
+// The present minute meeting a past note need not go unnoticed while the
+// canvas is out of sight. Each alert is chosen in `CHRONOS_ALERTS`: the
+// terminal bell, the window title (set through an OSC escape) and a desktop
+// notification run through a command of your own. `CHRONOS_ALERT_TAGS`
+// narrows them to notes carrying one of the listed tags.
+
+const TITLE: &str = "Chronos Aeternum";
+/// What `notify` runs unless `CHRONOS_NOTIFY_COMMAND` says otherwise; `$1` is the message.
+const DEFAULT_NOTIFY: &str = r#"notify-send "Chronos Aeternum" "$1""#;
+/// At dilated speeds minutes fly past; the bell and notifications keep at least this far apart.
+const QUIET_GAP: Duration = Duration::from_secs(20);
+
+#[derive(Default)]
+pub struct Alerts {
+    bell: bool,
+    title: bool,
+    /// A shell command run with the message as `$1`.
+    notify: Option<String>,
+    /// Only notes with one of these tags resonate; empty lets every note through.
+    tags: Vec<String>,
+    pub muted: bool,
+    /// The minute last listened to, so every minute alerts at most once.
+    heard: Option<(NaiveDate, u32, u32)>,
+    titled: bool,
+    last_sounded: Option<Instant>,
+}
+
+impl Alerts {
+    pub fn from_env() -> Result<Self> {
+        let mut alerts = Self::default();
+        let Ok(kinds) = std::env::var("CHRONOS_ALERTS") else { return Ok(alerts) };
+        for kind in kinds.split(',').map(str::trim).filter(|k| !k.is_empty()) {
+            match kind {
+                "bell" => alerts.bell = true,
+                "title" => alerts.title = true,
+                "notify" => {
+                    let command = std::env::var("CHRONOS_NOTIFY_COMMAND").unwrap_or_else(|_| DEFAULT_NOTIFY.to_string());
+                    alerts.notify = Some(command);
+                }
+                other => bail!("unknown alert {:?} in CHRONOS_ALERTS (use bell, title, notify)", other),
+            }
+        }
+        if let Ok(tags) = std::env::var("CHRONOS_ALERT_TAGS") {
+            alerts.tags = tags
+                .split(',')
+                .map(|tag| tag.trim().trim_start_matches('#').to_lowercase())
+                .filter(|tag| !tag.is_empty())
+                .collect();
+        }
+        Ok(alerts)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.bell || self.title || self.notify.is_some()
+    }
+
+    /// The window title goes back to the instrument's name once alerts have changed it.
+    pub fn restore_title(&mut self) {
+        if std::mem::take(&mut self.titled) {
+            let _ = execute!(io::stdout(), SetTitle(TITLE));
+        }
+    }
+
+    fn sound(&mut self, message: &str) -> Result<()> {
+        if self.title {
+            execute!(io::stdout(), SetTitle(format!("✦ {}", message)))?;
+            self.titled = true;
+        }
+        if self.last_sounded.is_some_and(|at| at.elapsed() < QUIET_GAP) {
+            return Ok(());
+        }
+        self.last_sounded = Some(Instant::now());
+        if self.bell {
+            let mut out = io::stdout();
+            out.write_all(b"\x07")?;
+            out.flush()?;
+        }
+        if let Some(command) = &self.notify {
+            let mut child = Command::new("sh")
+                .args(["-c", command, "chronos-aeternum", message])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+                .context("cannot run CHRONOS_NOTIFY_COMMAND")?;
+            // Reaped off the main loop, however long the notifier takes
+            thread::spawn(move || child.wait());
+        }
+        Ok(())
+    }
+}
+
+impl App<'_> {
+    /// Sounds the alerts once when the present (virtual) minute meets past notes.
+    pub fn listen_for_resonance(&mut self) {
+        if !self.alerts.is_enabled() {
+            return;
+        }
+        let now = self.virtual_time;
+        let minute = (now.date_naive(), now.hour(), now.minute());
+        let Some(heard) = self.alerts.heard.replace(minute) else { return };
+        if heard == minute {
+            return;
+        }
+
+        let tags = &self.alerts.tags;
+        let echoes: Vec<_> = resonance::echoes(&self.notes, minute.0, minute.1, minute.2, Reach::Minute)
+            .into_iter()
+            .filter(|echo| {
+                tags.is_empty()
+                    || self.notes.get(&echo.key).is_some_and(|note| {
+                        // A passphrase-sealed note shows no tags
+                        extract_tags(&note.content).iter().any(|tag| tags.contains(tag))
+                    })
+            })
+            .collect();
+        let Some(newest) = echoes.first() else {
+            self.alerts.restore_title();
+            return;
+        };
+        if self.alerts.muted {
+            return;
+        }
+
+        let note = &self.notes[&newest.key];
+        let first_line = if note.is_encrypted() { "" } else { note.content.lines().next().unwrap_or_default() };
+        let message = format!(
+            "{:02}:{:02} resonates with {} note(s): {} {}",
+            minute.1,
+            minute.2,
+            echoes.len(),
+            newest.date,
+            first_line.chars().take(60).collect::<String>(),
+        );
+        if let Err(e) = self.alerts.sound(message.trim_end()) {
+            self.status = Some(format!("RESONANCE ALERT FAILED: {:#}", e));
+        }
+    }
+
+    /// m: silences the alerts (or lets them sound again) without leaving the instrument.
+    pub fn toggle_alerts(&mut self) {
+        if !self.alerts.is_enabled() {
+            self.notify("NO RESONANCE ALERTS: SET CHRONOS_ALERTS TO bell, title AND/OR notify");
+            return;
+        }
+        self.alerts.muted = !self.alerts.muted;
+        if self.alerts.muted {
+            self.alerts.restore_title();
+        }
+        self.notify(if self.alerts.muted { "RESONANCE ALERTS MUTED" } else { "RESONANCE ALERTS ON" });
+    }
+}
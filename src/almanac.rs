@@ -0,0 +1,326 @@
+use chrono::NaiveDate;
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph, Sparkline},
+    Frame,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::{
+    extract_tags,
+    panels::{AMBER, GOLD, PALE},
+    parse_note_key,
+    presence::{self, Presence},
+    App, Screen, TimeNote,
+};
+
+// --- The Almanac ---
+// Counts kept over the whole archive: streaks of days written in, how much
+// was written, the minutes returned to most and the tags that recur. It is
+// tallied when opened and again whenever the rings regrow; the presence
+// beside it is read live from the ledger.
+
+/// How many days back the daily sparkline can reach.
+const SPARK_DAYS: usize = 365;
+
+pub struct Almanac {
+    pub notes: usize,
+    pub days_written: usize,
+    pub first_day: Option<NaiveDate>,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub per_day: f64,
+    pub per_week: f64,
+    pub words: usize,
+    /// Notes sealed under a passphrase, whose words cannot be counted.
+    pub sealed: usize,
+    pub busiest_minutes: Vec<((u32, u32), u32)>,
+    pub tags: Vec<(String, u32)>,
+    pub hours: [u64; 24],
+    /// Notes per day, oldest first, ending today.
+    pub daily: Vec<u64>,
+}
+
+impl Almanac {
+    pub fn tally(notes: &HashMap<String, TimeNote>, today: NaiveDate) -> Self {
+        let mut per_date: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+        let mut minutes: HashMap<(u32, u32), u32> = HashMap::new();
+        let mut tags: HashMap<String, u32> = HashMap::new();
+        let mut hours = [0; 24];
+        let (mut words, mut sealed) = (0, 0);
+
+        for (key, note) in notes {
+            let Some((date, h, m)) = parse_note_key(key) else { continue };
+            *per_date.entry(date).or_default() += 1;
+            *minutes.entry((h, m)).or_default() += 1;
+            hours[h as usize] += 1;
+            if note.is_encrypted() {
+                sealed += 1;
+                continue;
+            }
+            words += note.content.split_whitespace().count();
+            for tag in extract_tags(&note.content) {
+                *tags.entry(tag).or_default() += 1;
+            }
+        }
+
+        let days: BTreeSet<NaiveDate> = per_date.keys().copied().collect();
+        let first_day = days.first().copied();
+        let span_days = first_day.map_or(0, |first| (today - first).num_days().max(0) + 1) as f64;
+
+        let mut busiest_minutes: Vec<((u32, u32), u32)> = minutes.into_iter().filter(|&(_, c)| c > 1).collect();
+        busiest_minutes.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        busiest_minutes.truncate(8);
+
+        let mut tags: Vec<(String, u32)> = tags.into_iter().collect();
+        tags.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let daily = (0..SPARK_DAYS as i64)
+            .rev()
+            .map(|back| per_date.get(&(today - chrono::Duration::days(back))).copied().unwrap_or(0))
+            .collect();
+
+        Self {
+            notes: notes.len(),
+            days_written: days.len(),
+            first_day,
+            current_streak: current_streak(&days, today),
+            longest_streak: longest_streak(&days),
+            per_day: if span_days > 0.0 { notes.len() as f64 / span_days } else { 0.0 },
+            per_week: if span_days > 0.0 { notes.len() as f64 / span_days * 7.0 } else { 0.0 },
+            words,
+            sealed,
+            busiest_minutes,
+            tags,
+            hours,
+            daily,
+        }
+    }
+}
+
+/// Days written in a row, ending today; a streak still counts until a day without notes has fully passed.
+fn current_streak(days: &BTreeSet<NaiveDate>, today: NaiveDate) -> u32 {
+    let mut day = if days.contains(&today) { today } else { today - chrono::Duration::days(1) };
+    let mut streak = 0;
+    while days.contains(&day) {
+        streak += 1;
+        day -= chrono::Duration::days(1);
+    }
+    streak
+}
+
+fn longest_streak(days: &BTreeSet<NaiveDate>) -> u32 {
+    let (mut longest, mut run) = (0, 0);
+    let mut previous: Option<NaiveDate> = None;
+    for &day in days {
+        run = if previous.is_some_and(|p| day - p == chrono::Duration::days(1)) { run + 1 } else { 1 };
+        longest = longest.max(run);
+        previous = Some(day);
+    }
+    longest
+}
+
+impl App<'_> {
+    pub fn open_almanac(&mut self) {
+        self.almanac = Some(Almanac::tally(&self.notes, self.virtual_time.date_naive()));
+        self.screen = Screen::Almanac;
+    }
+
+    pub fn handle_almanac_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('a') => {
+                self.screen = Screen::Astrolabe;
+                self.almanac = None;
+            }
+            KeyCode::Char('q') | KeyCode::Char('Q') => self.should_quit = true,
+            _ => {}
+        }
+    }
+}
+
+pub fn draw_almanac(f: &mut Frame, app: &App) {
+    let Some(almanac) = &app.almanac else { return };
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(11),
+            Constraint::Length(6),
+            Constraint::Min(8),
+            Constraint::Length(1),
+        ])
+        .split(f.area());
+
+    f.render_widget(
+        Paragraph::new("THE ALMANAC OF PRESENCE")
+            .style(Style::default().fg(GOLD).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center),
+        rows[0],
+    );
+
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(34), Constraint::Percentage(26)])
+        .split(rows[1]);
+    draw_totals(f, top[0], almanac);
+    draw_presence(f, top[1], app);
+    draw_busiest_minutes(f, top[2], almanac);
+    draw_daily(f, rows[2], almanac);
+
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(rows[3]);
+    draw_hours(f, bottom[0], almanac);
+    draw_tags(f, bottom[1], almanac);
+
+    let hints = match &app.status {
+        Some(status) => Span::styled(status.clone(), Style::default().fg(Color::Red)),
+        None => Span::styled("a / Esc (Back) | Q (Quit)", Style::default().fg(Color::DarkGray)),
+    };
+    f.render_widget(Paragraph::new(Line::from(hints)), rows[4]);
+}
+
+fn panel(title: &str) -> Block<'_> {
+    Block::default().borders(Borders::ALL).title(format!(" {} ", title)).style(Style::default().fg(GOLD))
+}
+
+fn figure(label: &str, value: String) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(format!(" {:<17}", label), Style::default().fg(Color::DarkGray)),
+        Span::styled(value, Style::default().fg(PALE).add_modifier(Modifier::BOLD)),
+    ])
+}
+
+fn draw_totals(f: &mut Frame, area: Rect, almanac: &Almanac) {
+    let words = if almanac.sealed > 0 {
+        format!("{} (+{} unread)", almanac.words, almanac.sealed)
+    } else {
+        almanac.words.to_string()
+    };
+    let per_note = if almanac.notes > almanac.sealed {
+        almanac.words as f64 / (almanac.notes - almanac.sealed) as f64
+    } else {
+        0.0
+    };
+    let lines = vec![
+        figure("Current streak", format!("{} day(s)", almanac.current_streak)),
+        figure("Longest streak", format!("{} day(s)", almanac.longest_streak)),
+        figure("Notes", almanac.notes.to_string()),
+        figure("Days written in", almanac.days_written.to_string()),
+        figure("Since", almanac.first_day.map_or("-".to_string(), |d| d.to_string())),
+        figure("Notes per day", format!("{:.2}", almanac.per_day)),
+        figure("Notes per week", format!("{:.1}", almanac.per_week)),
+        figure("Words", words),
+        figure("Words per note", format!("{:.0}", per_note)),
+    ];
+    f.render_widget(Paragraph::new(lines).block(panel("STREAKS AND TOTALS")), area);
+}
+
+fn draw_presence(f: &mut Frame, area: Rect, app: &App) {
+    let ledger = app.ledger();
+    let today = chrono::Local::now().date_naive();
+    let lines = vec![
+        figure("Breathed today", presence::format_secs(ledger.on(Presence::Breathing, today))),
+        figure("Breathed", presence::format_secs(ledger.breathing_secs())),
+        figure("Written today", presence::format_secs(ledger.on(Presence::Writing, today))),
+        figure("Written", presence::format_secs(ledger.writing_secs())),
+        figure("Written per day", presence::format_secs(ledger.writing_per_day())),
+        figure("Sealed nodes", app.sealed_nodes.to_string()),
+        Line::raw(""),
+        figure("Experience units", ledger.experience_units(app.sealed_nodes).to_string()),
+    ];
+    f.render_widget(Paragraph::new(lines).block(panel("PRESENCE")), area);
+}
+
+fn draw_busiest_minutes(f: &mut Frame, area: Rect, almanac: &Almanac) {
+    let mut lines: Vec<Line> = almanac
+        .busiest_minutes
+        .iter()
+        .map(|&((h, m), count)| {
+            Line::from(vec![
+                Span::styled(format!(" {:02}:{:02}  ", h, m), Style::default().fg(AMBER)),
+                Span::raw(format!("{} day(s)", count)),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(" No minute returned to yet.", Style::default().fg(Color::DarkGray))));
+    }
+    f.render_widget(
+        Paragraph::new(lines).style(Style::default().fg(PALE)).block(panel("MINUTES RETURNED TO")),
+        area,
+    );
+}
+
+fn draw_daily(f: &mut Frame, area: Rect, almanac: &Almanac) {
+    // As many days as there are columns, ending today
+    let shown = (area.width.saturating_sub(2) as usize).min(almanac.daily.len());
+    let days = &almanac.daily[almanac.daily.len() - shown..];
+    f.render_widget(
+        Sparkline::default()
+            .block(panel(&format!("NOTES PER DAY, LAST {} DAYS", shown)))
+            .data(days)
+            .style(Style::default().fg(AMBER)),
+        area,
+    );
+}
+
+fn draw_hours(f: &mut Frame, area: Rect, almanac: &Almanac) {
+    let bars: Vec<Bar> = almanac
+        .hours
+        .iter()
+        .enumerate()
+        .map(|(h, &count)| Bar::default().value(count).label(Line::from(format!("{:02}", h))).text_value(String::new()))
+        .collect();
+    // Two columns and a gap per hour when there is room
+    let width = if area.width >= 2 + 24 * 3 { 2 } else { 1 };
+    f.render_widget(
+        BarChart::default()
+            .block(panel("TIME OF DAY"))
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(width)
+            .bar_gap(1)
+            .bar_style(Style::default().fg(AMBER))
+            .label_style(Style::default().fg(Color::DarkGray)),
+        area,
+    );
+}
+
+fn draw_tags(f: &mut Frame, area: Rect, almanac: &Almanac) {
+    if almanac.tags.is_empty() {
+        f.render_widget(
+            Paragraph::new(Span::styled(" No #tags written yet.", Style::default().fg(Color::DarkGray))).block(panel("TAGS")),
+            area,
+        );
+        return;
+    }
+    let shown = area.height.saturating_sub(2) as usize;
+    let bars: Vec<Bar> = almanac
+        .tags
+        .iter()
+        .take(shown)
+        .map(|(tag, count)| {
+            Bar::default()
+                .value(*count as u64)
+                .label(Line::from(format!("#{}", tag)))
+                .text_value(count.to_string())
+        })
+        .collect();
+    f.render_widget(
+        BarChart::default()
+            .block(panel("TAGS"))
+            .direction(Direction::Horizontal)
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(1)
+            .bar_gap(0)
+            .bar_style(Style::default().fg(AMBER))
+            .value_style(Style::default().fg(Color::Black).bg(AMBER))
+            .label_style(Style::default().fg(PALE)),
+        area,
+    );
+}
@@ -0,0 +1,109 @@
+use anyhow::Result;
+use std::{collections::HashMap, thread};
+use tokio::{runtime::Handle, sync::mpsc};
+
+use crate::{
+    presence::Ledger,
+    pulse::Pulse,
+    store::{SaveOutcome, Store},
+    sync::{self, SyncReport},
+    TimeNote,
+};
+
+// --- The Archivist ---
+// The only hand on the archive file while the instrument runs. It works on
+// its own thread, one job at a time, and answers through the pulse channel,
+// so a slow disk (or a slow sync) never holds up a frame.
+
+pub enum Job {
+    /// `settled` names drafts to drop once the notes are safely written.
+    Save { notes: HashMap<String, TimeNote>, settled: Vec<String> },
+    /// Keeps (or with `None`, drops) the draft of an open vault.
+    Draft { key: String, draft: Option<TimeNote> },
+    Load(LoadPurpose),
+    /// Reads the archive only when something else changed it.
+    Poll,
+    Sync(String),
+    /// Adds presence to the ledger on disk.
+    Presence(Ledger),
+}
+
+#[derive(Clone, Copy)]
+pub enum LoadPurpose {
+    Merge,
+    Reload,
+}
+
+pub enum Reply {
+    /// The notes that were handed in, so they can become the new baseline.
+    Saved { notes: HashMap<String, TimeNote>, outcome: Result<SaveOutcome> },
+    Loaded { purpose: LoadPurpose, result: Result<HashMap<String, TimeNote>> },
+    Polled(Result<Option<HashMap<String, TimeNote>>>),
+    Drafted(Result<()>),
+    /// The archive as the sync left it.
+    Synced(Result<(SyncReport, HashMap<String, TimeNote>)>),
+    /// The presence that was handed in, and the whole ledger once it was added.
+    Banked { banked: Ledger, result: Result<Ledger> },
+}
+
+pub struct Archivist {
+    jobs: mpsc::UnboundedSender<Job>,
+}
+
+impl Archivist {
+    pub fn spawn(mut store: Store, pulses: mpsc::UnboundedSender<Pulse>) -> Self {
+        let (jobs, mut queue) = mpsc::unbounded_channel();
+        let runtime = Handle::current();
+        let mut drafts = store.drafts();
+
+        thread::spawn(move || {
+            while let Some(job) = queue.blocking_recv() {
+                let reply = match job {
+                    Job::Save { notes, settled } => {
+                        let outcome = store.save(&notes);
+                        if matches!(outcome, Ok(SaveOutcome::Written)) && !settled.is_empty() {
+                            // The notes are safe; a draft left behind only costs a restore offer.
+                            let _ = drafts.update_notes(|d| {
+                                for key in &settled {
+                                    d.remove(key);
+                                }
+                            });
+                        }
+                        Reply::Saved { notes, outcome }
+                    }
+                    Job::Draft { key, draft } => Reply::Drafted(drafts.update_notes(|d| {
+                        match draft {
+                            Some(draft) => d.insert(key, draft),
+                            None => d.remove(&key),
+                        };
+                    })),
+                    Job::Load(purpose) => Reply::Loaded { purpose, result: store.load() },
+                    Job::Poll => Reply::Polled(match store.changed_on_disk() {
+                        Ok(true) => store.load().map(Some),
+                        Ok(false) => Ok(None),
+                        Err(e) => Err(e),
+                    }),
+                    Job::Sync(url) => Reply::Synced(
+                        runtime
+                            .block_on(sync::sync(&mut store, &url))
+                            .and_then(|report| Ok((report, store.load()?))),
+                    ),
+                    Job::Presence(banked) => {
+                        let result = store.presence().update_value(|ledger: &mut Ledger| ledger.absorb(&banked));
+                        Reply::Banked { banked, result }
+                    }
+                };
+                if pulses.send(Pulse::Disk(reply)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self { jobs }
+    }
+
+    pub fn send(&self, job: Job) {
+        // The worker only stops once the instrument has stopped listening.
+        let _ = self.jobs.send(job);
+    }
+}
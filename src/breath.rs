@@ -0,0 +1,323 @@
+use chrono::Timelike;
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        canvas::{Canvas, Circle},
+        Block, Borders, Paragraph,
+    },
+    Frame,
+};
+use std::time::Instant;
+
+use crate::{
+    note_key,
+    panels::{AMBER, GOLD, PALE},
+    presence, App, Screen, TimeNote,
+};
+
+// --- Guided Breath ---
+// The golden breath around the astrolabe follows a fixed 4-1-8 cycle. A
+// session slows down to breathe on purpose: a chosen pattern, paced in real
+// seconds whatever the dilation of time, with the phase named on screen.
+// Every session that completes at least one full breath is logged to the
+// archive as a note at the minute it ends.
+
+/// Seconds of inhale, hold, exhale and hold of the breath that always runs around the astrolabe.
+pub const GOLDEN: [u32; 4] = [4, 1, 8, 0];
+/// The seconds a phase of a custom pattern may last.
+const LONGEST_PHASE: u32 = 20;
+const LONGEST_SESSION_MINUTES: u32 = 60;
+
+pub struct Pattern {
+    pub name: &'static str,
+    pub phases: [u32; 4],
+}
+
+const PATTERNS: [Pattern; 3] = [
+    Pattern { name: "Box", phases: [4, 4, 4, 4] },
+    Pattern { name: "Relaxing", phases: [4, 7, 8, 0] },
+    Pattern { name: "Coherent", phases: [5, 0, 5, 0] },
+];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Phase {
+    Inhale,
+    Hold,
+    Exhale,
+    /// The pause with empty lungs.
+    Rest,
+}
+
+impl Phase {
+    const ORDER: [Phase; 4] = [Phase::Inhale, Phase::Hold, Phase::Exhale, Phase::Rest];
+
+    /// Holds may last no time at all; inhale and exhale cannot.
+    fn is_hold(self) -> bool {
+        matches!(self, Phase::Hold | Phase::Rest)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Phase::Inhale => "Inhale",
+            Phase::Hold | Phase::Rest => "Hold",
+            Phase::Exhale => "Exhale",
+        }
+    }
+}
+
+/// `[4, 7, 8, 0]` -> `4-7-8`; holds of no length are left out.
+pub fn describe(phases: [u32; 4]) -> String {
+    let shown: Vec<String> = Phase::ORDER
+        .into_iter()
+        .zip(phases)
+        .filter(|&(phase, secs)| secs > 0 || !phase.is_hold())
+        .map(|(_, secs)| secs.to_string())
+        .collect();
+    shown.join("-")
+}
+
+fn cycle_secs(phases: [u32; 4]) -> f64 {
+    phases.iter().sum::<u32>().max(1) as f64
+}
+
+/// How full the lungs are `t` seconds into breathing `phases`, from 0 to 1,
+/// with the phase under way and the seconds left of it.
+pub fn breath_at(phases: [u32; 4], t: f64) -> (f64, Phase, f64) {
+    let mut t = t.rem_euclid(cycle_secs(phases));
+    for (phase, secs) in Phase::ORDER.into_iter().zip(phases.map(f64::from)) {
+        if t < secs {
+            let fullness = match phase {
+                Phase::Inhale => t / secs,
+                Phase::Hold => 1.0,
+                Phase::Exhale => 1.0 - t / secs,
+                Phase::Rest => 0.0,
+            };
+            return (fullness, phase, secs - t);
+        }
+        t -= secs;
+    }
+    (0.0, Phase::Inhale, 0.0)
+}
+
+pub struct Session {
+    /// Into `PATTERNS`; one past the end is the custom pattern.
+    pattern: usize,
+    custom: [u32; 4],
+    /// The phase of the custom pattern that + and - change.
+    focus: usize,
+    minutes: u32,
+    started: Option<Instant>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self { pattern: 0, custom: [4, 2, 6, 2], focus: 0, minutes: 5, started: None }
+    }
+}
+
+impl Session {
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    fn is_custom(&self) -> bool {
+        self.pattern == PATTERNS.len()
+    }
+
+    pub fn phases(&self) -> [u32; 4] {
+        PATTERNS.get(self.pattern).map_or(self.custom, |p| p.phases)
+    }
+
+    fn name(&self) -> &'static str {
+        PATTERNS.get(self.pattern).map_or("Custom", |p| p.name)
+    }
+
+    /// Seconds breathed so far, while a session runs.
+    pub fn elapsed(&self) -> Option<f64> {
+        self.started.map(|at| at.elapsed().as_secs_f64())
+    }
+
+    fn length_secs(&self) -> f64 {
+        self.minutes as f64 * 60.0
+    }
+
+    fn cycles(&self, elapsed: f64) -> u32 {
+        (elapsed / cycle_secs(self.phases())) as u32
+    }
+
+    fn adjust_custom(&mut self, by: i32) {
+        let least = if Phase::ORDER[self.focus].is_hold() { 0 } else { 1 };
+        let secs = &mut self.custom[self.focus];
+        *secs = secs.saturating_add_signed(by).clamp(least, LONGEST_PHASE);
+    }
+}
+
+impl App<'_> {
+    pub fn handle_breath_key(&mut self, key: KeyEvent) {
+        if self.breath.is_running() {
+            if matches!(key.code, KeyCode::Esc | KeyCode::Enter) {
+                self.end_breath_session();
+            }
+            return;
+        }
+        let session = &mut self.breath;
+        let choices = PATTERNS.len() + 1;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('b') => self.screen = Screen::Astrolabe,
+            KeyCode::Char('q') | KeyCode::Char('Q') => self.should_quit = true,
+            KeyCode::Left => session.pattern = (session.pattern + choices - 1) % choices,
+            KeyCode::Right => session.pattern = (session.pattern + 1) % choices,
+            KeyCode::Up => session.minutes = (session.minutes + 1).min(LONGEST_SESSION_MINUTES),
+            KeyCode::Down => session.minutes = session.minutes.saturating_sub(1).max(1),
+            KeyCode::Tab if session.is_custom() => session.focus = (session.focus + 1) % 4,
+            KeyCode::Char('+') if session.is_custom() => session.adjust_custom(1),
+            KeyCode::Char('-') if session.is_custom() => session.adjust_custom(-1),
+            KeyCode::Enter => {
+                session.started = Some(Instant::now());
+                self.notice = None;
+                if !self.save_failed {
+                    self.status = None;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Ends the session once its length is breathed; called every tick.
+    pub fn tend_breath(&mut self) {
+        if self.breath.elapsed().is_some_and(|elapsed| elapsed >= self.breath.length_secs()) {
+            self.end_breath_session();
+        }
+    }
+
+    /// Stops the session and logs it as a note at the present minute.
+    fn end_breath_session(&mut self) {
+        let Some(elapsed) = self.breath.elapsed() else { return };
+        self.breath.started = None;
+        let elapsed = elapsed.min(self.breath.length_secs());
+        let cycles = self.breath.cycles(elapsed);
+        if cycles == 0 {
+            self.notify("SESSION ENDED BEFORE A FULL BREATH: NOTHING LOGGED");
+            return;
+        }
+
+        let ended_early = elapsed < self.breath.length_secs();
+        let line = format!(
+            "Guided breath: {} {} for {}, {} cycle(s){}. #breath",
+            self.breath.name(),
+            describe(self.breath.phases()),
+            presence::format_secs(elapsed),
+            cycles,
+            if ended_early { ", ended early" } else { "" },
+        );
+        let now = self.virtual_time;
+        let key = note_key(now.date_naive(), now.hour(), now.minute());
+        let note = match self.notes.get(&key) {
+            Some(note) if note.is_encrypted() => {
+                self.status = Some("SESSION NOT LOGGED: THIS MINUTE IS SEALED UNDER A PASSPHRASE".to_string());
+                return;
+            }
+            Some(note) => {
+                let mut note = note.clone();
+                note.content = format!("{}\n{}", note.content.trim_end(), line);
+                note.touch();
+                note
+            }
+            None => TimeNote::new(line),
+        };
+        self.notes.insert(key, note);
+        self.persist();
+        self.notify(format!("SESSION LOGGED AT {}", now.format("%H:%M")));
+    }
+}
+
+pub fn draw_breath(f: &mut Frame, app: &App) {
+    let session = &app.breath;
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Length(2), Constraint::Min(8), Constraint::Length(5), Constraint::Length(1)])
+        .split(f.area());
+
+    f.render_widget(
+        Paragraph::new("GUIDED BREATH")
+            .style(Style::default().fg(GOLD).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center),
+        rows[0],
+    );
+
+    // Before a session starts the circle previews the chosen pattern
+    let t = session.elapsed().unwrap_or_else(|| app.virtual_time.timestamp_millis() as f64 / 1000.0);
+    let (fullness, phase, left) = breath_at(session.phases(), t);
+    let canvas = Canvas::default()
+        .x_bounds([-100.0, 100.0])
+        .y_bounds([-100.0, 100.0])
+        .paint(move |ctx| {
+            ctx.draw(&Circle { x: 0.0, y: 0.0, radius: 90.0, color: Color::Rgb(40, 40, 40) });
+            let radius = 15.0 + 70.0 * fullness;
+            ctx.draw(&Circle { x: 0.0, y: 0.0, radius, color: GOLD });
+            ctx.draw(&Circle { x: 0.0, y: 0.0, radius: radius * 0.7, color: Color::Rgb(150, 120, 40) });
+            ctx.draw(&Circle { x: 0.0, y: 0.0, radius: radius * 0.4, color: Color::Rgb(90, 72, 24) });
+        });
+    f.render_widget(canvas, rows[1]);
+
+    let lines = match session.elapsed() {
+        Some(elapsed) => vec![
+            Line::from(Span::styled(phase.label().to_uppercase(), Style::default().fg(PALE).add_modifier(Modifier::BOLD))),
+            Line::from(Span::styled(format!("{:.0}", left.ceil()), Style::default().fg(AMBER))),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(
+                    "Cycle {}  ·  {} {}  ·  {} left",
+                    session.cycles(elapsed) + 1,
+                    session.name(),
+                    describe(session.phases()),
+                    presence::format_secs((session.length_secs() - elapsed).max(0.0)),
+                ),
+                Style::default().fg(Color::DarkGray),
+            )),
+        ],
+        None => {
+            let mut pattern = vec![Span::styled(format!("◂ {} ", session.name()), Style::default().fg(PALE))];
+            for (i, secs) in session.phases().iter().enumerate() {
+                let style = if session.is_custom() && i == session.focus {
+                    Style::default().fg(Color::Black).bg(AMBER)
+                } else {
+                    Style::default().fg(AMBER)
+                };
+                if i > 0 {
+                    pattern.push(Span::raw("-"));
+                }
+                pattern.push(Span::styled(format!("{} {}", Phase::ORDER[i].label(), secs), style));
+            }
+            pattern.push(Span::styled(" ▸", Style::default().fg(PALE)));
+            vec![
+                Line::from(pattern),
+                Line::from(Span::styled(format!("{} minute(s)", session.minutes), Style::default().fg(PALE))),
+                Line::from(""),
+                Line::from(Span::styled("Enter to begin", Style::default().fg(Color::DarkGray))),
+            ]
+        }
+    };
+    f.render_widget(
+        Paragraph::new(lines).alignment(Alignment::Center).block(Block::default().borders(Borders::TOP).style(Style::default().fg(GOLD))),
+        rows[2],
+    );
+
+    let hints = match (app.message(), session.is_running()) {
+        (Some(message), false) => message,
+        (_, true) => Span::styled("Esc / Enter (End and log the session)", Style::default().fg(Color::DarkGray)),
+        (None, false) => Span::styled(
+            if session.is_custom() {
+                "←→ (Pattern) | ↑↓ (Minutes) | Tab (Phase) | +/- (Seconds) | Enter (Begin) | b / Esc (Back) | Q (Quit)"
+            } else {
+                "←→ (Pattern) | ↑↓ (Minutes) | Enter (Begin) | b / Esc (Back) | Q (Quit)"
+            },
+            Style::default().fg(Color::DarkGray),
+        ),
+    };
+    f.render_widget(Paragraph::new(Line::from(hints)), rows[3]);
+}
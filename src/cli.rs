@@ -0,0 +1,334 @@
+use anyhow::{bail, Context, Result};
+use ratatui::crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, BufWriter, IsTerminal, Write},
+    path::{Path, PathBuf},
+};
+use zeroize::Zeroizing;
+
+use crate::{
+    html, ics, merge, records,
+    store::{SaveOutcome, Store},
+    sync, TimeNote, SAVE_FILE,
+};
+
+// --- Command Line Rituals ---
+// Without arguments the instrument opens its TUI; with a subcommand it works
+// on the archive headlessly and returns.
+
+const USAGE: &str = "\
+USAGE:
+    chronos-aeternum                      Open the instrument
+    chronos-aeternum export --format <FORMAT> [--output <FILE>]
+    chronos-aeternum import <FILE.ics>
+    chronos-aeternum merge <OTHER.json> [--base <ANCESTOR.json>]
+    chronos-aeternum serve [--bind <ADDR>]  Serve the archive for sync (default 127.0.0.1:7341)
+    chronos-aeternum sync <URL>           Exchange changes with a served archive, e.g. http://127.0.0.1:7341
+    chronos-aeternum encrypt              Seal the archive under a (new) passphrase
+    chronos-aeternum decrypt              Store the archive as plaintext again
+
+A sealed archive asks for its passphrase, or reads it from CHRONOS_PASSPHRASE.
+When CHRONOS_SYNC_TOKEN is set, serve requires it and sync sends it; serve needs it
+to serve a sealed archive or to listen beyond this machine.
+The instrument sounds resonance alerts chosen in CHRONOS_ALERTS (bell, title, notify),
+narrowed by CHRONOS_ALERT_TAGS; notify runs CHRONOS_NOTIFY_COMMAND with the message as $1.
+
+EXPORT FORMATS:
+    ics           Notes as one-minute VEVENT entries
+    ics-journal   Notes as VJOURNAL entries
+    html          The tree rings as a self-contained page with hover tooltips
+    svg           The tree rings as a bare SVG image
+    csv           One row per note: date, hour, minute, weekday, length, tags, lock state
+    jsonl         The same rows as JSON lines
+";
+
+pub fn run(args: &[String]) -> Result<()> {
+    match args[0].as_str() {
+        "export" => export(&args[1..]),
+        "import" => import(&args[1..]),
+        "merge" => merge(&args[1..]),
+        "serve" => serve(&args[1..]),
+        "sync" => sync(&args[1..]),
+        "encrypt" => encrypt(),
+        "decrypt" => decrypt(),
+        "help" | "--help" | "-h" => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        other => bail!("unknown command `{}`\n\n{}", other, USAGE),
+    }
+}
+
+fn export(args: &[String]) -> Result<()> {
+    let format = flag_value(args, "--format")?.context("export needs --format")?;
+    let output = flag_value(args, "--output")?;
+
+    let format = match format.as_str() {
+        "ics" => Export::Calendar(ics::Component::Event),
+        "ics-journal" => Export::Calendar(ics::Component::Journal),
+        "html" => Export::Rings(html::Page::Html),
+        "svg" => Export::Rings(html::Page::Svg),
+        "csv" => Export::Records(records::Format::Csv),
+        "jsonl" => Export::Records(records::Format::Jsonl),
+        other => bail!("unknown export format `{}`\n\n{}", other, USAGE),
+    };
+    // Loaded before the output is created, so a failed load leaves an earlier export intact
+    let notes = open_store()?.load()?;
+
+    let mut out: Box<dyn Write> = match &output {
+        Some(path) => Box::new(fs::File::create(path).with_context(|| format!("cannot create {}", path))?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut out = BufWriter::new(&mut out);
+    match format {
+        Export::Calendar(component) => ics::export(&notes, component, &mut out)?,
+        Export::Rings(page) => html::export(&notes, page, &mut out)?,
+        Export::Records(records) => records::export(&notes, records, &mut out)?,
+    }
+    out.flush()?;
+    Ok(())
+}
+
+enum Export {
+    Calendar(ics::Component),
+    Rings(html::Page),
+    Records(records::Format),
+}
+
+fn import(args: &[String]) -> Result<()> {
+    let path = args.first().context("import needs a file")?;
+    let source = fs::read_to_string(path).with_context(|| format!("cannot read {}", path))?;
+
+    let mut store = open_store()?;
+    let mut notes = store.load()?;
+    let report = ics::import(&source, &mut notes);
+    if report.planted > 0 {
+        save(&mut store, &notes)?;
+    }
+
+    println!(
+        "{} placeholder notes planted, {} minutes already occupied, {} entries unreadable",
+        report.planted, report.occupied, report.unreadable
+    );
+    if report.zoned > 0 {
+        println!(
+            "  {} of them start in a named time zone (TZID), planted at its wall-clock time; those minutes may be off",
+            report.zoned
+        );
+    }
+    Ok(())
+}
+
+/// Folds another archive into this one. The other archive as it was merged is
+/// remembered as the common ancestor for the next merge with it.
+fn merge(args: &[String]) -> Result<()> {
+    let other_path = args.first().filter(|a| !a.starts_with("--")).context("merge needs the other archive")?;
+    let mut store = open_store()?;
+    let ours = store.load()?;
+
+    let mut other = open_store_at(other_path)?;
+    let theirs = other.load()?;
+
+    let other_name = Path::new(other_path).file_stem().and_then(|s| s.to_str()).unwrap_or("other").to_string();
+    let mut snapshot = store.ancestor(&other_name);
+
+    let base = match flag_value(args, "--base")? {
+        Some(path) => Some(open_store_at(&path)?.load()?),
+        None if snapshot.path().exists() => Some(snapshot.load()?),
+        None => None,
+    };
+
+    let merged = merge::merge(base.as_ref(), &ours, &theirs, &other_name);
+    save(&mut store, &merged.notes)?;
+    snapshot.replace(&theirs)?;
+
+    println!(
+        "Merged {} into {}{}",
+        other_path,
+        store.path().display(),
+        if base.is_some() { " against their common ancestor" } else { " by modification time (no common ancestor yet)" }
+    );
+    println!(
+        "  {} minutes taken from {}, {} conflicting, {} notes in total",
+        merged.taken,
+        other_name,
+        merged.conflicts.len(),
+        merged.notes.len()
+    );
+    for conflict in &merged.conflicts {
+        let (kept, aside) = match conflict.kept {
+            merge::Side::Ours => ("this archive's", other_name.as_str()),
+            merge::Side::Theirs => (other_name.as_str(), "this archive's"),
+        };
+        println!("  CONFLICT {}: kept {} version (newer), {} version saved as a revision", conflict.key, kept, aside);
+    }
+    Ok(())
+}
+
+fn serve(args: &[String]) -> Result<()> {
+    let addr = flag_value(args, "--bind")?.unwrap_or_else(|| sync::DEFAULT_ADDR.to_string());
+    let store = open_store()?;
+    tokio::runtime::Runtime::new()?.block_on(sync::serve(store, &addr))
+}
+
+fn sync(args: &[String]) -> Result<()> {
+    let url = args.first().context("sync needs the URL of a served archive")?;
+    let mut store = open_store()?;
+    let report = tokio::runtime::Runtime::new()?.block_on(sync::sync(&mut store, url))?;
+
+    println!(
+        "Synced with {}{}",
+        url,
+        if report.had_ancestor { "" } else { " (first sync: no common ancestor yet)" }
+    );
+    println!("  {} minutes pulled, {} pushed, {} conflicting", report.pulled, report.pushed, report.conflicts.len());
+    for key in &report.conflicts {
+        println!("  CONFLICT {}: both versions kept, the newer as current", key);
+    }
+    Ok(())
+}
+
+fn encrypt() -> Result<()> {
+    let mut store = open_store()?;
+    let notes = store.load()?;
+
+    let passphrase = read_passphrase("New passphrase: ")?;
+    let confirmation = read_passphrase("Repeat passphrase: ")?;
+    if passphrase != confirmation {
+        bail!("the passphrases differ; the archive is unchanged");
+    }
+    if passphrase.is_empty() {
+        bail!("an empty passphrase seals nothing; the archive is unchanged");
+    }
+    let companions = companions(&store)?;
+
+    store.set_passphrase(Some(&passphrase))?;
+    save(&mut store, &notes)?;
+    println!("Archive sealed. It will ask for this passphrase before the canvas opens.");
+    reseal(&store, companions, "sealed")?;
+    Ok(())
+}
+
+fn decrypt() -> Result<()> {
+    let mut store = open_store()?;
+    if !store.is_encrypted() {
+        println!("The archive is not sealed.");
+        return Ok(());
+    }
+    let notes = store.load()?;
+    let companions = companions(&store)?;
+    store.set_passphrase(None)?;
+    save(&mut store, &notes)?;
+    println!("Archive unsealed; it is stored as plaintext again.");
+    reseal(&store, companions, "unsealed")?;
+    Ok(())
+}
+
+/// The files beside the archive that hold notes or presence of their own
+/// (drafts, the presence ledger, merge ancestors and migration backups), read
+/// while the old key still opens them.
+fn companions(store: &Store) -> Result<Vec<(PathBuf, Value)>> {
+    let mut companions = Vec::new();
+    let siblings = [store.drafts(), store.presence()].into_iter().chain(store.ancestors()?).chain(store.backups()?);
+    for companion in siblings {
+        if let Some(value) = companion.load_value()? {
+            companions.push((companion.path().to_path_buf(), value));
+        }
+    }
+    Ok(companions)
+}
+
+/// Writes the companions again under the archive's new passphrase (or none), naming each.
+fn reseal(store: &Store, companions: Vec<(PathBuf, Value)>, done: &str) -> Result<()> {
+    for (path, value) in companions {
+        store.sibling(&path).replace_value::<Value>(&value)?;
+        println!("  {} {} as well", path.display(), done);
+    }
+    Ok(())
+}
+
+/// Saves, refusing to replace notes another instance wrote in the meantime.
+fn save(store: &mut Store, notes: &HashMap<String, TimeNote>) -> Result<()> {
+    match store.save(notes)? {
+        SaveOutcome::Written => Ok(()),
+        SaveOutcome::ChangedOnDisk => bail!("the archive changed on disk meanwhile; nothing was written, please retry"),
+    }
+}
+
+/// The archive store, unlocked when it is sealed.
+fn open_store() -> Result<Store> {
+    let mut store = Store::new(SAVE_FILE);
+    if store.is_sealed()? {
+        let passphrase = match std::env::var("CHRONOS_PASSPHRASE") {
+            Ok(passphrase) => Zeroizing::new(passphrase),
+            Err(_) => read_passphrase("Archive passphrase: ")?,
+        };
+        store.unlock(&passphrase)?;
+    }
+    Ok(store)
+}
+
+/// Some other archive file, asking for its own passphrase when it is sealed.
+fn open_store_at(path: &str) -> Result<Store> {
+    let mut store = Store::new(path);
+    if !store.path().exists() {
+        bail!("{} does not exist", path);
+    }
+    if store.is_sealed()? {
+        store.unlock(&read_passphrase(&format!("Passphrase for {}: ", path))?)?;
+    }
+    Ok(store)
+}
+
+/// Reads a secret without echoing it; falls back to a plain line when stdin is not a terminal.
+fn read_passphrase(label: &str) -> Result<Zeroizing<String>> {
+    eprint!("{}", label);
+    io::stderr().flush()?;
+
+    let mut passphrase = Zeroizing::new(String::new());
+    if !io::stdin().is_terminal() {
+        io::stdin().read_line(&mut passphrase)?;
+        let len = passphrase.trim_end_matches(['\r', '\n']).len();
+        passphrase.truncate(len);
+        return Ok(passphrase);
+    }
+
+    enable_raw_mode()?;
+    let outcome = loop {
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => break Ok(()),
+            KeyCode::Esc => break Err(anyhow::anyhow!("cancelled")),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break Err(anyhow::anyhow!("cancelled")),
+            KeyCode::Backspace => {
+                passphrase.pop();
+            }
+            KeyCode::Char(c) => passphrase.push(c),
+            _ => {}
+        }
+    };
+    disable_raw_mode()?;
+    eprintln!();
+
+    outcome.map(|()| passphrase)
+}
+
+/// Value following `name`, e.g. `--format ics`.
+fn flag_value(args: &[String], name: &str) -> Result<Option<String>> {
+    match args.iter().position(|a| a == name) {
+        Some(i) => match args.get(i + 1) {
+            Some(value) => Ok(Some(value.clone())),
+            None => bail!("{} needs a value", name),
+        },
+        None => Ok(None),
+    }
+}
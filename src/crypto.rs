@@ -0,0 +1,140 @@
+use anyhow::{anyhow, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    XChaCha20Poly1305, XNonce,
+};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+// --- Resin Seal ---
+// Passphrase-derived authenticated encryption: Argon2id stretches the
+// passphrase into a key, XChaCha20-Poly1305 seals the bytes. Everything
+// needed to re-derive the key (except the passphrase) travels with the box.
+
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+/// Ceilings on the costs a sealed file may ask for, a few times the defaults,
+/// so a tampered file cannot make an unlock allocate gigabytes.
+const MAX_M_COST: u32 = 4 * 64 * 1024;
+const MAX_T_COST: u32 = 12;
+const MAX_P_COST: u32 = 4;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub algorithm: String,
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    #[serde(with = "b64")]
+    pub salt: Vec<u8>,
+}
+
+impl KdfParams {
+    /// Fresh salt with the current default costs (64 MiB, 3 passes).
+    pub fn generate() -> Self {
+        let mut salt = vec![0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        Self { algorithm: "argon2id".to_string(), m_cost: 64 * 1024, t_cost: 3, p_cost: 1, salt }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SealedBox {
+    pub cipher: String,
+    pub kdf: KdfParams,
+    #[serde(with = "b64")]
+    pub nonce: Vec<u8>,
+    #[serde(with = "b64")]
+    pub ciphertext: Vec<u8>,
+}
+
+/// A derived key together with the parameters that produced it.
+#[derive(Clone)]
+pub struct Key {
+    bytes: Zeroizing<[u8; KEY_LEN]>,
+    kdf: KdfParams,
+}
+
+impl Key {
+    pub fn derive(passphrase: &str, kdf: &KdfParams) -> Result<Self> {
+        if kdf.algorithm != "argon2id" {
+            return Err(anyhow!("unsupported key derivation `{}`", kdf.algorithm));
+        }
+        if kdf.m_cost > MAX_M_COST || kdf.t_cost > MAX_T_COST || kdf.p_cost > MAX_P_COST {
+            return Err(anyhow!(
+                "key derivation costs (m={}, t={}, p={}) exceed what this build accepts; the file may be damaged",
+                kdf.m_cost,
+                kdf.t_cost,
+                kdf.p_cost
+            ));
+        }
+        let params = Params::new(kdf.m_cost, kdf.t_cost, kdf.p_cost, Some(KEY_LEN))
+            .map_err(|e| anyhow!("invalid key derivation parameters: {}", e))?;
+        let mut bytes = Zeroizing::new([0u8; KEY_LEN]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &kdf.salt, bytes.as_mut())
+            .map_err(|e| anyhow!("key derivation failed: {}", e))?;
+        Ok(Self { bytes, kdf: kdf.clone() })
+    }
+
+    pub fn seal(&self, plaintext: &[u8]) -> Result<SealedBox> {
+        let cipher = XChaCha20Poly1305::new(self.bytes.as_ref().into());
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| anyhow!("encryption failed"))?;
+        Ok(SealedBox {
+            cipher: "xchacha20poly1305".to_string(),
+            kdf: self.kdf.clone(),
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })
+    }
+
+    /// Fails when the box was sealed under a different passphrase or tampered with.
+    pub fn open(&self, sealed: &SealedBox) -> Result<Zeroizing<Vec<u8>>> {
+        if sealed.cipher != "xchacha20poly1305" {
+            return Err(anyhow!("unsupported cipher `{}`", sealed.cipher));
+        }
+        if sealed.nonce.len() != 24 {
+            return Err(anyhow!("malformed nonce"));
+        }
+        let cipher = XChaCha20Poly1305::new(self.bytes.as_ref().into());
+        cipher
+            .decrypt(XNonce::from_slice(&sealed.nonce), sealed.ciphertext.as_ref())
+            .map(Zeroizing::new)
+            .map_err(|_| anyhow!("wrong passphrase, or the sealed data was altered"))
+    }
+}
+
+mod b64 {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        STANDARD.decode(text).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_costs_beyond_the_ceiling() {
+        for kdf in [
+            KdfParams { m_cost: u32::MAX, ..KdfParams::generate() },
+            KdfParams { t_cost: 1000, ..KdfParams::generate() },
+            KdfParams { p_cost: 64, ..KdfParams::generate() },
+        ] {
+            let error = Key::derive("passphrase", &kdf).err().expect("costs beyond the ceiling");
+            assert!(error.to_string().contains("exceed"));
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{archivist::Job, parse_note_key, App, Revision, TimeNote};
+
+// --- Drafts ---
+// While the vault is open, its contents are kept as a draft every few
+// seconds, so a dying terminal costs at most those seconds. A draft is
+// dropped once its note is safely written; drafts still around at launch
+// are offered back.
+
+impl App<'_> {
+    /// Keeps the open vault's contents as a draft when they changed since the last one.
+    pub fn keep_draft(&mut self) {
+        if !self.is_editing {
+            return;
+        }
+        let Some(key) = self.editing_key.clone() else { return };
+        let content = self.textarea.lines().join("\n");
+        if self.last_draft.as_deref() == Some(content.as_str()) {
+            return;
+        }
+
+        let mut draft = match self.notes.get(&key) {
+            Some(note) => TimeNote { content: content.clone(), sealed: None, revisions: Vec::new(), ..note.clone() },
+            None => TimeNote::new(content.clone()),
+        };
+        draft.touch();
+        // A passphrase-sealed node is drafted under its own seal, never in the clear
+        if let Some(vault_key) = &self.vault_key {
+            match vault_key.seal(draft.content.as_bytes()) {
+                Ok(sealed) => {
+                    draft.content.clear();
+                    draft.sealed = Some(sealed);
+                }
+                Err(e) => {
+                    self.status = Some(format!("DRAFT NOT KEPT: {:#}", e));
+                    return;
+                }
+            }
+        }
+
+        self.last_draft = Some(content);
+        self.archivist.send(Job::Draft { key, draft: Some(draft) });
+    }
+
+    /// Offers back the drafts that differ from the notes they belong to.
+    pub fn offer_drafts(&mut self, drafts: HashMap<String, TimeNote>) {
+        let unsaved: HashMap<String, TimeNote> = drafts
+            .into_iter()
+            .filter(|(key, draft)| !self.notes.get(key).is_some_and(|note| note.same_substance(draft)))
+            .collect();
+        if !unsaved.is_empty() {
+            self.drafts = Some(unsaved);
+        }
+    }
+
+    pub fn handle_drafts_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('r') | KeyCode::Char('R') => self.restore_drafts(),
+            KeyCode::Char('d') | KeyCode::Char('D') => self.discard_drafts(),
+            // Offered again at the next launch
+            KeyCode::Esc => self.drafts = None,
+            _ => {}
+        }
+    }
+
+    fn restore_drafts(&mut self) {
+        let Some(drafts) = self.drafts.take() else { return };
+        for (key, draft) in drafts {
+            let mut note = match self.notes.get(&key) {
+                Some(previous) => {
+                    let mut note = TimeNote { content: draft.content, sealed: draft.sealed, ..previous.clone() };
+                    // The note changed again after the draft was kept; that version stays as well
+                    if previous.modified > draft.modified {
+                        note.revisions.push(Revision {
+                            content: previous.content.clone(),
+                            sealed: previous.sealed.clone(),
+                            modified: previous.modified,
+                            source: "before draft restore".to_string(),
+                        });
+                    }
+                    note
+                }
+                None => draft,
+            };
+            note.touch();
+            self.notes.insert(key.clone(), note);
+            self.settled_drafts.push(key);
+        }
+        self.persist();
+    }
+
+    fn discard_drafts(&mut self) {
+        let Some(drafts) = self.drafts.take() else { return };
+        for key in drafts.into_keys() {
+            self.archivist.send(Job::Draft { key, draft: None });
+        }
+    }
+}
+
+pub fn draw_drafts(f: &mut Frame, area: Rect, drafts: &HashMap<String, TimeNote>) {
+    let gold = Color::Rgb(212, 175, 55);
+    f.render_widget(Clear, area);
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(format!(" {} observation node(s) were being written when the instrument last closed:", drafts.len())),
+    ];
+    let sorted: BTreeMap<&String, &TimeNote> = drafts.iter().collect();
+    for (key, draft) in sorted.iter().take(3) {
+        let when = parse_note_key(key)
+            .map(|(date, h, m)| format!("{} {:02}:{:02}", date, h, m))
+            .unwrap_or_else(|| key.to_string());
+        let preview = if draft.is_encrypted() {
+            "(sealed under its own passphrase)".to_string()
+        } else {
+            draft.content.lines().next().unwrap_or_default().chars().take(48).collect()
+        };
+        text.push(Line::from(vec![
+            Span::styled(format!("   {} ", when), Style::default().fg(gold)),
+            Span::raw(preview),
+        ]));
+    }
+    if drafts.len() > 3 {
+        text.push(Line::from(format!("   ... and {} more", drafts.len() - 3)));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled(" [R]", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+        Span::raw(" Restore   "),
+        Span::styled("[D]", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+        Span::raw(" Discard   "),
+        Span::styled("[ESC]", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+        Span::raw(" Ask again next time"),
+    ]));
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().fg(gold))
+        .title(Span::styled(" UNSAVED DRAFTS ", Style::default().fg(gold).add_modifier(Modifier::BOLD)))
+        .title_alignment(Alignment::Left);
+    f.render_widget(
+        Paragraph::new(text).style(Style::default().fg(Color::Rgb(252, 246, 186))).block(block),
+        area,
+    );
+}
@@ -0,0 +1,270 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    panels::{AMBER, GOLD, PALE},
+    parse_note_key, rings, App, Screen, TimeNote,
+};
+
+// --- Presence Heatmap ---
+// The archive seen from above: when in the week presence gathers, and how
+// it spread over a calendar year. Every cell leads back to the astrolabe,
+// onto the ring and minute it counts.
+
+/// From an empty cell to the fullest one.
+const SHADES: [Color; 5] = [
+    Color::Rgb(30, 26, 14),
+    Color::Rgb(90, 62, 8),
+    Color::Rgb(150, 104, 0),
+    Color::Rgb(210, 150, 0),
+    AMBER,
+];
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Grid {
+    /// Weekdays down, hours across.
+    Week { weekday: u32, hour: u32 },
+    /// Weekdays down, the weeks of a year across.
+    Year(NaiveDate),
+}
+
+pub struct Heatmap {
+    pub grid: Grid,
+}
+
+impl Heatmap {
+    pub fn new(today: NaiveDate) -> Self {
+        Self { grid: Grid::Week { weekday: today.weekday().num_days_from_monday(), hour: 12 } }
+    }
+}
+
+/// Notes per weekday (Monday first) and hour.
+fn by_weekday_hour(notes: &HashMap<String, TimeNote>) -> [[u32; 24]; 7] {
+    let mut grid = [[0; 24]; 7];
+    for key in notes.keys() {
+        if let Some((date, h, _)) = parse_note_key(key) {
+            grid[date.weekday().num_days_from_monday() as usize][h as usize] += 1;
+        }
+    }
+    grid
+}
+
+/// Notes per calendar day.
+fn by_day(notes: &HashMap<String, TimeNote>) -> HashMap<NaiveDate, u32> {
+    let mut days = HashMap::new();
+    for key in notes.keys() {
+        if let Some((date, _, _)) = parse_note_key(key) {
+            *days.entry(date).or_default() += 1;
+        }
+    }
+    days
+}
+
+fn shade(count: u32, peak: u32) -> Color {
+    if count == 0 {
+        return SHADES[0];
+    }
+    let level = 1.0 + 3.0 * rings::density(count, peak);
+    SHADES[(level.round() as usize).min(SHADES.len() - 1)]
+}
+
+/// The Monday on or before the first of January, where the year grid starts.
+fn year_start(year: i32) -> NaiveDate {
+    let first = NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or_default();
+    rings::Period::Week.start_of(first)
+}
+
+impl App<'_> {
+    pub fn handle_heatmap_key(&mut self, key: KeyEvent) {
+        let today = self.virtual_time.date_naive();
+        let grid = &mut self.heatmap.grid;
+        match (key.code, *grid) {
+            (KeyCode::Esc, _) | (KeyCode::Char('h'), _) => self.screen = Screen::Astrolabe,
+            (KeyCode::Char('q'), _) | (KeyCode::Char('Q'), _) => self.should_quit = true,
+            (KeyCode::Tab, Grid::Week { .. }) => *grid = Grid::Year(today),
+            (KeyCode::Tab, Grid::Year(_)) => *grid = Heatmap::new(today).grid,
+            (KeyCode::Enter, _) => self.jump_from_heatmap(),
+
+            (KeyCode::Left, Grid::Week { weekday, hour }) => *grid = Grid::Week { weekday, hour: (hour + 23) % 24 },
+            (KeyCode::Right, Grid::Week { weekday, hour }) => *grid = Grid::Week { weekday, hour: (hour + 1) % 24 },
+            (KeyCode::Up, Grid::Week { weekday, hour }) => *grid = Grid::Week { weekday: (weekday + 6) % 7, hour },
+            (KeyCode::Down, Grid::Week { weekday, hour }) => *grid = Grid::Week { weekday: (weekday + 1) % 7, hour },
+
+            (KeyCode::Left, Grid::Year(date)) => *grid = Grid::Year(date - chrono::Duration::days(7)),
+            (KeyCode::Right, Grid::Year(date)) => *grid = Grid::Year(date + chrono::Duration::days(7)),
+            (KeyCode::Up, Grid::Year(date)) => *grid = Grid::Year(date.pred_opt().unwrap_or(date)),
+            (KeyCode::Down, Grid::Year(date)) => *grid = Grid::Year(date.succ_opt().unwrap_or(date)),
+            (KeyCode::Char('['), Grid::Year(date)) => *grid = Grid::Year(date - chrono::Months::new(12)),
+            (KeyCode::Char(']'), Grid::Year(date)) => *grid = Grid::Year(date + chrono::Months::new(12)),
+            _ => {}
+        }
+    }
+
+    /// Back to the astrolabe, onto the ring and minute of the first note the cell counts.
+    fn jump_from_heatmap(&mut self) {
+        let target = match self.heatmap.grid {
+            // The most recent such weekday with a note in that hour
+            Grid::Week { weekday, hour } => self
+                .notes
+                .keys()
+                .filter_map(|key| parse_note_key(key))
+                .filter(|(date, h, _)| date.weekday().num_days_from_monday() == weekday && *h == hour)
+                .max_by_key(|(date, _, m)| (*date, std::cmp::Reverse(*m))),
+            Grid::Year(day) => self
+                .notes
+                .keys()
+                .filter_map(|key| parse_note_key(key))
+                .filter(|(date, _, _)| *date == day)
+                .min_by_key(|&(_, h, m)| (h, m)),
+        };
+        let Some((date, hour, minute)) = target else {
+            self.notify("NO NOTES IN THIS CELL");
+            return;
+        };
+
+        if !self.select_node(date, hour, minute) {
+            self.notify(format!("{} IS NOT IN THIS RING LAYOUT", date));
+            return;
+        }
+        self.screen = Screen::Astrolabe;
+    }
+}
+
+pub fn draw_heatmap(f: &mut Frame, app: &App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Length(10),
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(f.area());
+
+    let title = match app.heatmap.grid {
+        Grid::Week { .. } => "PRESENCE BY WEEKDAY AND HOUR".to_string(),
+        Grid::Year(date) => format!("PRESENCE THROUGH {}", date.year()),
+    };
+    f.render_widget(
+        Paragraph::new(title)
+            .style(Style::default().fg(GOLD).add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center),
+        rows[0],
+    );
+
+    let (lines, detail) = match app.heatmap.grid {
+        Grid::Week { weekday, hour } => week_grid(&app.notes, weekday, hour, rows[1].width),
+        Grid::Year(date) => year_grid(&app.notes, date, rows[1].width),
+    };
+    f.render_widget(
+        Paragraph::new(lines).block(Block::default().borders(Borders::ALL).style(Style::default().fg(GOLD))),
+        rows[1],
+    );
+
+    let mut legend = vec![Span::styled(detail, Style::default().fg(PALE)), Span::raw("    less ")];
+    legend.extend(SHADES.iter().map(|&c| Span::styled("■", Style::default().fg(c))));
+    legend.push(Span::raw(" more"));
+    f.render_widget(Paragraph::new(Line::from(legend)), rows[2]);
+
+    let hints = match app.message() {
+        Some(message) => message,
+        None => Span::styled(
+            "Arrows (Move) | Tab (Week / Year) | [ / ] (Year) | Enter (Go to ring) | h / Esc (Back) | Q (Quit)",
+            Style::default().fg(Color::DarkGray),
+        ),
+    };
+    f.render_widget(Paragraph::new(Line::from(hints)), rows[4]);
+}
+
+fn week_grid(notes: &HashMap<String, TimeNote>, weekday: u32, hour: u32, width: u16) -> (Vec<Line<'static>>, String) {
+    let grid = by_weekday_hour(notes);
+    let peak = grid.iter().flatten().copied().max().unwrap_or(0);
+    // Three columns per hour when there is room, else two
+    let cell = if width >= 4 + 2 + 24 * 3 { 3 } else { 2 };
+
+    let mut header = vec![Span::raw("     ")];
+    for h in 0..24 {
+        let label = if h % 3 == 0 { format!("{:<width$}", h, width = cell * 3) } else { String::new() };
+        header.push(Span::styled(label, Style::default().fg(Color::DarkGray)));
+    }
+    let mut lines = vec![Line::from(header)];
+
+    for (d, hours) in grid.iter().enumerate() {
+        let name = Weekday::try_from(d as u8).map(|w| w.to_string()).unwrap_or_default();
+        let mut spans = vec![Span::styled(format!(" {:<4}", name), Style::default().fg(GOLD))];
+        for (h, &count) in hours.iter().enumerate() {
+            let selected = d as u32 == weekday && h as u32 == hour;
+            spans.push(cell_span(count, peak, selected, cell));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let count = grid[weekday as usize][hour as usize];
+    let name = Weekday::try_from(weekday as u8).map(|w| w.to_string()).unwrap_or_default();
+    (lines, format!("{} {:02}:00-{:02}:59  {} note(s)", name, hour, hour, count))
+}
+
+fn year_grid(notes: &HashMap<String, TimeNote>, cursor: NaiveDate, width: u16) -> (Vec<Line<'static>>, String) {
+    let days = by_day(notes);
+    let year = cursor.year();
+    let start = year_start(year);
+    let in_year: Vec<u32> = days.iter().filter(|(d, _)| d.year() == year).map(|(_, &c)| c).collect();
+    let peak = in_year.iter().copied().max().unwrap_or(0);
+    let cell = if width >= 4 + 2 + 54 * 2 { 2 } else { 1 };
+
+    // Month names over the week in which each month begins
+    let mut header: BTreeMap<usize, String> = BTreeMap::new();
+    for month in 1..=12 {
+        if let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) {
+            header.insert(((first - start).num_days() / 7) as usize, first.format("%b").to_string());
+        }
+    }
+    let mut top = String::from("     ");
+    for (week, name) in header {
+        let column = 5 + week * cell;
+        if top.chars().count() <= column {
+            top.push_str(&" ".repeat(column - top.chars().count()));
+            top.push_str(&name);
+        }
+    }
+    let mut lines = vec![Line::from(Span::styled(top, Style::default().fg(Color::DarkGray)))];
+
+    for d in 0..7 {
+        let name = Weekday::try_from(d as u8).map(|w| w.to_string()).unwrap_or_default();
+        let mut spans = vec![Span::styled(format!(" {:<4}", name), Style::default().fg(GOLD))];
+        for week in 0..54 {
+            let date = start + chrono::Duration::days(week * 7 + d);
+            if date.year() != year {
+                spans.push(Span::raw(" ".repeat(cell)));
+                continue;
+            }
+            let count = days.get(&date).copied().unwrap_or(0);
+            spans.push(cell_span(count, peak, date == cursor, cell));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let count = days.get(&cursor).copied().unwrap_or(0);
+    let total: u32 = in_year.iter().sum();
+    (lines, format!("{}  {} note(s)   ({} in {})", cursor.format("%a %Y-%m-%d"), count, total, year))
+}
+
+fn cell_span(count: u32, peak: u32, selected: bool, width: usize) -> Span<'static> {
+    let block = "■".to_string() + &" ".repeat(width.saturating_sub(1));
+    let style = Style::default().fg(shade(count, peak));
+    if selected {
+        Span::styled(block, style.bg(PALE))
+    } else {
+        Span::styled(block, style)
+    }
+}
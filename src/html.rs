@@ -0,0 +1,184 @@
+use std::{collections::HashMap, fmt::Write as _, io::Write};
+
+use crate::{
+    note_key,
+    rings::{self, CANVAS_BOUND, INNER_WATCH_RADIUS},
+    TimeNote,
+};
+
+// --- Static Tree Ring Export ---
+// Renders the dendrochronology with the same geometry as the terminal canvas.
+// Canvas space has y pointing up; SVG space has it pointing down, so every
+// y coordinate is negated on the way out.
+
+const GOLD: &str = "#d4af37";
+const GOLD_DIM: &str = "#645014";
+const AMBER: &str = "#ffbf00";
+const RING: &str = "#282828";
+const SEALED: &str = "#b22222";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Page {
+    /// A bare SVG document; tooltips come from `<title>` elements.
+    Svg,
+    /// A self-contained HTML page with styled hover tooltips.
+    Html,
+}
+
+pub fn export(notes: &HashMap<String, TimeNote>, page: Page, out: &mut impl Write) -> std::io::Result<()> {
+    let svg = render_svg(notes);
+    match page {
+        Page::Svg => {
+            out.write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")?;
+            out.write_all(svg.as_bytes())
+        }
+        Page::Html => {
+            out.write_all(HTML_HEAD.as_bytes())?;
+            out.write_all(svg.as_bytes())?;
+            out.write_all(HTML_TAIL.as_bytes())
+        }
+    }
+}
+
+fn render_svg(notes: &HashMap<String, TimeNote>) -> String {
+    // One ring per date, as the instrument opens; the daily layout does not look at today
+    let rings = rings::grow_rings(notes, &rings::Growth::default(), chrono::Local::now().date_naive());
+    let outermost = rings.last().map_or(0.0, |ring| ring.radius);
+    let bound = CANVAS_BOUND.max(outermost + 20.0);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{:.0} {:.0} {:.0} {:.0}\" class=\"chronos\">",
+        -bound, -bound, bound * 2.0, bound * 2.0
+    );
+    let _ = writeln!(
+        svg,
+        "<rect x=\"{:.0}\" y=\"{:.0}\" width=\"{:.0}\" height=\"{:.0}\" fill=\"#000\"/>",
+        -bound, -bound, bound * 2.0, bound * 2.0
+    );
+
+    // --- Dendrochronology ---
+    for ring in &rings {
+        let radius = ring.radius;
+        let _ = writeln!(
+            svg,
+            "<circle class=\"ring\" r=\"{:.2}\" fill=\"none\" stroke=\"{}\"><title>{}</title></circle>",
+            radius, RING, ring.date.format("%Y-%m-%d")
+        );
+
+        for &(h, m) in ring.times.keys() {
+            let key = note_key(ring.date, h, m);
+            let Some(note) = notes.get(&key) else { continue };
+            let (x, y) = polar(radius, rings::node_angle_deg(h, m));
+            let label = format!("{} {:02}:{:02}", ring.date.format("%Y-%m-%d"), h, m);
+            let (fill, content) = if note.is_encrypted() {
+                (SEALED, "Sealed under its own passphrase.")
+            } else {
+                (AMBER, note.content.as_str())
+            };
+            if note.is_locked {
+                let _ = writeln!(svg, "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"3.5\" fill=\"none\" stroke=\"{}\"/>", x, -y, GOLD);
+            }
+            let _ = writeln!(
+                svg,
+                "<circle class=\"node\" cx=\"{:.2}\" cy=\"{:.2}\" r=\"2\" fill=\"{}\" data-when=\"{}\" data-note=\"{}\"><title>{}\n{}</title></circle>",
+                x, -y, fill, label, escape(content), label, escape(content)
+            );
+        }
+    }
+
+    // --- Inner Watch ---
+    for i in 0..60 {
+        let angle = rings::tick_angle_deg(i as f64);
+        let (x1, y1) = polar(INNER_WATCH_RADIUS - 2.0, angle);
+        let (x2, y2) = polar(INNER_WATCH_RADIUS, angle);
+        let color = if i % 5 == 0 { GOLD } else { GOLD_DIM };
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"{}\"/>",
+            x1, -y1, x2, -y2, color
+        );
+    }
+
+    // Vector Lotus Crown, frozen at rest
+    for i in 0..12 {
+        let angle = 90.0 - i as f64 * 30.0;
+        let side = 6.0;
+        let (xa, ya) = polar(rings::PETAL_RADIUS, angle);
+        let (xl, yl) = polar(102.0, angle - side);
+        let (xr, yr) = polar(102.0, angle + side);
+        let _ = writeln!(
+            svg,
+            "<polyline points=\"{:.2},{:.2} {:.2},{:.2} {:.2},{:.2}\" fill=\"none\" stroke=\"{}\"/>",
+            xl, -yl, xa, -ya, xr, -yr, GOLD
+        );
+    }
+
+    let _ = writeln!(svg, "<circle r=\"3\" fill=\"none\" stroke=\"{}\"/>", GOLD);
+    let _ = writeln!(svg, "<circle r=\"1\" fill=\"#fff\"/>");
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn polar(radius: f64, angle_deg: f64) -> (f64, f64) {
+    let rad = angle_deg.to_radians();
+    (radius * rad.cos(), radius * rad.sin())
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const HTML_HEAD: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Chronos Aeternum - Dendrochronology</title>
+<style>
+  html, body { margin: 0; height: 100%; background: #000; color: #d4af37; font-family: monospace; }
+  h1 { text-align: center; font-size: 1em; letter-spacing: 0.3em; margin: 1em 0 0; }
+  svg.chronos { display: block; margin: 0 auto; height: calc(100% - 3em); max-width: 100%; }
+  svg.chronos .node { cursor: pointer; }
+  svg.chronos .node:hover { fill: #ffff00; r: 4; }
+  svg.chronos .ring:hover { stroke: #645014; }
+  #tooltip { position: fixed; display: none; max-width: 32em; padding: 0.6em 0.8em; white-space: pre-wrap;
+             background: #0d0d0f; border: 1px solid #d4af37; color: #fcf6ba; pointer-events: none; }
+  #tooltip b { display: block; color: #d4af37; margin-bottom: 0.4em; }
+</style>
+</head>
+<body>
+<h1>* CHRONOS PLANTACERIUM * AETERNUM PRECISION ARCHIVE</h1>
+"#;
+
+const HTML_TAIL: &str = r#"<div id="tooltip"></div>
+<script>
+  const tip = document.getElementById("tooltip");
+  for (const node of document.querySelectorAll("svg.chronos .node")) {
+    node.querySelector("title").remove();
+    node.addEventListener("mousemove", (e) => {
+      tip.innerHTML = "";
+      const when = document.createElement("b");
+      when.textContent = node.dataset.when;
+      tip.append(when, node.dataset.note);
+      tip.style.display = "block";
+      tip.style.left = (e.clientX + 14) + "px";
+      tip.style.top = (e.clientY + 14) + "px";
+    });
+    node.addEventListener("mouseleave", () => { tip.style.display = "none"; });
+  }
+</script>
+</body>
+</html>
+"#;
@@ -0,0 +1,330 @@
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use std::{collections::HashMap, io::Write};
+
+use crate::{note_key, parse_note_key, TimeNote};
+
+// --- iCalendar Bridge ---
+// Notes leave the archive as one-minute calendar entries and calendar events
+// enter it as placeholder notes at their start minute.
+
+const PRODID: &str = "-//Plantacerium//Chronos Aeternum//EN";
+const FOLD_WIDTH: usize = 75;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Component {
+    Event,
+    Journal,
+}
+
+pub struct ImportReport {
+    pub planted: usize,
+    pub occupied: usize,
+    pub unreadable: usize,
+    /// Planted from a start in a named time zone (`TZID`), at that zone's
+    /// wall-clock time; the minute may be off from local time.
+    pub zoned: usize,
+}
+
+pub fn export(notes: &HashMap<String, TimeNote>, component: Component, out: &mut impl Write) -> std::io::Result<()> {
+    let mut keys: Vec<&String> = notes.keys().collect();
+    keys.sort();
+
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    write_line(out, "BEGIN:VCALENDAR")?;
+    write_line(out, "VERSION:2.0")?;
+    write_line(out, &format!("PRODID:{}", PRODID))?;
+    write_line(out, "CALSCALE:GREGORIAN")?;
+
+    for key in keys {
+        let Some((date, hour, minute)) = parse_note_key(key) else { continue };
+        let Some(start) = date.and_hms_opt(hour, minute, 0) else { continue };
+        let note = &notes[key];
+
+        let name = match component {
+            Component::Event => "VEVENT",
+            Component::Journal => "VJOURNAL",
+        };
+        write_line(out, &format!("BEGIN:{}", name))?;
+        write_line(out, &format!("UID:{}@chronos-aeternum", key))?;
+        write_line(out, &format!("DTSTAMP:{}", stamp))?;
+        write_line(out, &format!("DTSTART:{}", start.format("%Y%m%dT%H%M%S")))?;
+        if component == Component::Event {
+            let end = start + Duration::minutes(1);
+            write_line(out, &format!("DTEND:{}", end.format("%Y%m%dT%H%M%S")))?;
+            write_line(out, "TRANSP:TRANSPARENT")?;
+        }
+        if note.is_encrypted() {
+            // The passphrase never leaves the vault, so neither does the content.
+            write_line(out, "SUMMARY:Sealed Observation")?;
+        } else {
+            write_line(out, &format!("SUMMARY:{}", escape_text(&summary_of(&note.content))))?;
+            write_line(out, &format!("DESCRIPTION:{}", escape_text(&note.content)))?;
+        }
+        if note.is_locked {
+            write_line(out, "CLASS:PRIVATE")?;
+        }
+        write_line(out, &format!("END:{}", name))?;
+    }
+
+    write_line(out, "END:VCALENDAR")
+}
+
+pub fn import(source: &str, notes: &mut HashMap<String, TimeNote>) -> ImportReport {
+    let mut report = ImportReport { planted: 0, occupied: 0, unreadable: 0, zoned: 0 };
+
+    let mut in_entry = false;
+    // Components opened inside the current entry (VALARM and the like); their
+    // properties describe the component, not the entry.
+    let mut nested = 0usize;
+    let mut start: Option<NaiveDateTime> = None;
+    let mut zoned = false;
+    let mut summary = String::new();
+    let mut description = String::new();
+
+    for line in unfold(source) {
+        let Some((name, params, value)) = split_property(&line) else { continue };
+        match name.as_str() {
+            "BEGIN" if in_entry => nested += 1,
+            "END" if nested > 0 => nested -= 1,
+            "BEGIN" if value == "VEVENT" || value == "VJOURNAL" => {
+                in_entry = true;
+                start = None;
+                zoned = false;
+                summary.clear();
+                description.clear();
+            }
+            "END" if in_entry && (value == "VEVENT" || value == "VJOURNAL") => {
+                in_entry = false;
+                let Some(start) = start.take() else {
+                    report.unreadable += 1;
+                    continue;
+                };
+                let key = note_key(start.date(), start.hour(), start.minute());
+                if notes.contains_key(&key) {
+                    report.occupied += 1;
+                    continue;
+                }
+                let content = match (summary.is_empty(), description.is_empty()) {
+                    (_, true) => summary.clone(),
+                    (true, false) => description.clone(),
+                    (false, false) if description.starts_with(&summary) => description.clone(),
+                    (false, false) => format!("{}\n\n{}", summary, description),
+                };
+                notes.insert(key, TimeNote::new(content));
+                report.planted += 1;
+                if zoned {
+                    report.zoned += 1;
+                }
+            }
+            _ if nested > 0 => {}
+            "DTSTART" if in_entry => {
+                start = parse_start(&value);
+                zoned = params.iter().any(|param| param.to_ascii_uppercase().starts_with("TZID="));
+            }
+            "SUMMARY" if in_entry => summary = unescape_text(&value),
+            "DESCRIPTION" if in_entry => description = unescape_text(&value),
+            _ => {}
+        }
+    }
+
+    report
+}
+
+/// First meaningful line of a note, stripped of Markdown heading marks.
+fn summary_of(content: &str) -> String {
+    content
+        .lines()
+        .map(|l| l.trim().trim_start_matches('#').trim())
+        .find(|l| !l.is_empty())
+        .unwrap_or("Temporal Observation")
+        .to_string()
+}
+
+/// Resolves a DTSTART value to local wall-clock time. UTC stamps are shifted
+/// into the local zone; TZID and floating stamps are taken as local already
+/// (the import counts the TZID ones), and all-day dates land on the first
+/// minute of the day.
+fn parse_start(value: &str) -> Option<NaiveDateTime> {
+    if !value.contains('T') {
+        return NaiveDate::parse_from_str(value, "%Y%m%d").ok()?.and_hms_opt(0, 0, 0);
+    }
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(Local.from_utc_datetime(&naive).naive_local());
+    }
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()
+}
+
+/// Splits `NAME;PARAM=..:VALUE` into its name, parameters and value.
+fn split_property(line: &str) -> Option<(String, Vec<&str>, String)> {
+    let colon = line.find(':')?;
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next().unwrap_or(head);
+    Some((name.to_ascii_uppercase(), parts.collect(), value.to_string()))
+}
+
+fn unfold(source: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in source.lines() {
+        let raw = raw.trim_end_matches('\r');
+        if let Some(rest) = raw.strip_prefix([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(rest);
+                continue;
+            }
+        }
+        lines.push(raw.to_string());
+    }
+    lines
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape_text(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            plain.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => plain.push('\n'),
+            Some(other) => plain.push(other),
+            None => {}
+        }
+    }
+    plain
+}
+
+/// Writes a content line, folding it at 75 octets without splitting a character.
+fn write_line(out: &mut impl Write, line: &str) -> std::io::Result<()> {
+    let mut rest = line;
+    let mut width = FOLD_WIDTH;
+    while rest.len() > width {
+        let mut cut = width;
+        while !rest.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        out.write_all(&rest.as_bytes()[..cut])?;
+        out.write_all(b"\r\n ")?;
+        rest = &rest[cut..];
+        width = FOLD_WIDTH - 1;
+    }
+    out.write_all(rest.as_bytes())?;
+    out.write_all(b"\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calendar(entries: &str) -> String {
+        format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{}END:VCALENDAR\r\n", entries)
+    }
+
+    fn import_all(source: &str) -> (HashMap<String, TimeNote>, ImportReport) {
+        let mut notes = HashMap::new();
+        let report = import(source, &mut notes);
+        (notes, report)
+    }
+
+    #[test]
+    fn unfolds_continued_lines() {
+        let source = calendar("BEGIN:VEVENT\r\nDTSTART:20260102T090000\r\nSUMMARY:Morning\r\n  pages\r\n\tagain\r\nEND:VEVENT\r\n");
+        let (notes, report) = import_all(&source);
+        assert_eq!(report.planted, 1);
+        assert_eq!(notes["2026-01-02-09-00"].content, "Morning pagesagain");
+    }
+
+    #[test]
+    fn unescapes_text() {
+        assert_eq!(unescape_text(r"rain\, wind\nand sun\; \\ done"), "rain, wind\nand sun; \\ done");
+        assert_eq!(unescape_text(&escape_text("a, b;\nc\\")), "a, b;\nc\\");
+    }
+
+    #[test]
+    fn ignores_properties_of_nested_components() {
+        let source = calendar(
+            "BEGIN:VEVENT\r\nDTSTART:20260102T090000\r\nSUMMARY:Standup\r\n\
+             BEGIN:VALARM\r\nACTION:DISPLAY\r\nDESCRIPTION:This is an event reminder\r\nEND:VALARM\r\n\
+             END:VEVENT\r\n",
+        );
+        let (notes, _) = import_all(&source);
+        assert_eq!(notes["2026-01-02-09-00"].content, "Standup");
+    }
+
+    #[test]
+    fn shifts_utc_starts_into_local_time() {
+        let source = calendar("BEGIN:VEVENT\r\nDTSTART:20260102T090000Z\r\nSUMMARY:Call\r\nEND:VEVENT\r\n");
+        let (notes, _) = import_all(&source);
+        let utc = NaiveDate::from_ymd_opt(2026, 1, 2).unwrap().and_hms_opt(9, 0, 0).unwrap();
+        let local = Local.from_utc_datetime(&utc).naive_local();
+        assert!(notes.contains_key(&note_key(local.date(), local.hour(), local.minute())));
+    }
+
+    #[test]
+    fn plants_all_day_events_at_midnight() {
+        let source = calendar("BEGIN:VEVENT\r\nDTSTART;VALUE=DATE:20260102\r\nSUMMARY:Holiday\r\nEND:VEVENT\r\n");
+        let (notes, _) = import_all(&source);
+        assert_eq!(notes["2026-01-02-00-00"].content, "Holiday");
+    }
+
+    #[test]
+    fn counts_zoned_starts() {
+        let source = calendar("BEGIN:VEVENT\r\nDTSTART;TZID=America/New_York:20260102T090000\r\nSUMMARY:NY\r\nEND:VEVENT\r\n");
+        let (notes, report) = import_all(&source);
+        assert!(notes.contains_key("2026-01-02-09-00"));
+        assert_eq!((report.planted, report.zoned), (1, 1));
+    }
+
+    #[test]
+    fn counts_events_without_a_start_as_unreadable() {
+        let (notes, report) = import_all(&calendar("BEGIN:VEVENT\r\nSUMMARY:Whenever\r\nEND:VEVENT\r\n"));
+        assert!(notes.is_empty());
+        assert_eq!((report.planted, report.unreadable), (0, 1));
+    }
+
+    #[test]
+    fn leaves_occupied_minutes_alone() {
+        let mut notes = HashMap::from([("2026-01-02-09-00".to_string(), TimeNote::new("mine".to_string()))]);
+        let source = calendar("BEGIN:VEVENT\r\nDTSTART:20260102T090000\r\nSUMMARY:Standup\r\nEND:VEVENT\r\n");
+        let report = import(&source, &mut notes);
+        assert_eq!((report.planted, report.occupied), (0, 1));
+        assert_eq!(notes["2026-01-02-09-00"].content, "mine");
+    }
+
+    #[test]
+    fn round_trips_through_export() {
+        let long = format!("Morning pages, written slowly\n{}", "ünïcode and plain words; ".repeat(8));
+        let notes = HashMap::from([
+            ("2026-01-02-09-00".to_string(), TimeNote::new(long)),
+            ("2026-01-03-21-45".to_string(), TimeNote::new("Dusk".to_string())),
+        ]);
+        let mut out = Vec::new();
+        export(&notes, Component::Event, &mut out).unwrap();
+        let source = String::from_utf8(out).unwrap();
+        assert!(source.split("\r\n").all(|line| line.len() <= FOLD_WIDTH));
+
+        let (imported, report) = import_all(&source);
+        assert_eq!(report.planted, 2);
+        for (key, note) in &notes {
+            assert_eq!(imported[key].content, note.content);
+        }
+    }
+}
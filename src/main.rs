@@ -1,7 +1,7 @@
 use anyhow::Result;
-use chrono::{DateTime, Duration, Local, Timelike};
+use chrono::{DateTime, Duration, Local, NaiveDate, Timelike, Utc};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -20,34 +20,124 @@ use ratatui::{
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
-    fs,
     io,
-    time::{self, Instant},
+    panic::{self, AssertUnwindSafe},
+    thread, time,
 };
+use tokio::sync::mpsc;
 use tui_textarea::TextArea;
 
+use archivist::Archivist;
+use passphrase::{PassphrasePrompt, PromptAction};
+use pulse::Pulse;
+use store::Store;
+
+mod alerts;
+mod almanac;
+mod archivist;
+mod breath;
+mod cli;
+mod crypto;
+mod drafts;
+mod heatmap;
+mod html;
+mod ics;
+mod panels;
+mod passphrase;
+mod merge;
+mod mouse;
+mod presence;
+mod pulse;
+mod records;
+mod reconcile;
+mod resonance;
+mod rings;
+mod store;
+mod sync;
+mod vault;
+mod view;
+
 // --- Data Structures ---
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 struct TimeNote {
     content: String,
     is_locked: bool,
+    /// Content sealed under the note's own passphrase; `content` stays empty meanwhile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sealed: Option<crypto::SealedBox>,
+    /// When the note last changed, for merging archives that grew apart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    modified: Option<DateTime<Utc>>,
+    /// Versions set aside when two archives disagreed about this minute.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    revisions: Vec<Revision>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Revision {
+    content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sealed: Option<crypto::SealedBox>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    modified: Option<DateTime<Utc>>,
+    /// Which archive the version came from.
+    source: String,
+}
+
+impl TimeNote {
+    fn new(content: String) -> Self {
+        Self { content, is_locked: false, sealed: None, modified: Some(Utc::now()), revisions: Vec::new() }
+    }
+
+    fn is_encrypted(&self) -> bool {
+        self.sealed.is_some()
+    }
+
+    fn touch(&mut self) {
+        self.modified = Some(Utc::now());
+    }
+
+    /// Same words and seal, regardless of when they were written or what was set aside.
+    fn same_substance(&self, other: &TimeNote) -> bool {
+        self.content == other.content && self.is_locked == other.is_locked && self.sealed == other.sealed
+    }
 }
 
 const SAVE_FILE: &str = "chronos_notes.json";
 
-fn load_notes() -> HashMap<String, TimeNote> {
-    if let Ok(data) = fs::read_to_string(SAVE_FILE) {
-        serde_json::from_str(&data).unwrap_or_default()
-    } else {
-        HashMap::new()
+/// Builds the Minute-Level Key (`YYYY-MM-DD-HH-mm`) a note is banked under.
+fn note_key(date: NaiveDate, hour: u32, minute: u32) -> String {
+    format!("{}-{:02}-{:02}", date.format("%Y-%m-%d"), hour, minute)
+}
+
+/// Splits a Minute-Level Key back into its date, hour and minute.
+fn parse_note_key(key: &str) -> Option<(NaiveDate, u32, u32)> {
+    let parts: Vec<&str> = key.split('-').collect();
+    if parts.len() != 5 {
+        return None;
     }
+    let date = NaiveDate::parse_from_str(&parts[..3].join("-"), "%Y-%m-%d").ok()?;
+    let hour = parts[3].parse::<u32>().ok().filter(|h| *h < 24)?;
+    let minute = parts[4].parse::<u32>().ok().filter(|m| *m < 60)?;
+    Some((date, hour, minute))
 }
 
-fn save_notes(notes: &HashMap<String, TimeNote>) {
-    if let Ok(data) = serde_json::to_string_pretty(notes) {
-        let _ = fs::write(SAVE_FILE, data);
+/// `#hashtags` written anywhere in a note, lowercased and deduplicated.
+fn extract_tags(content: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for word in content.split_whitespace() {
+        let Some(tag) = word.strip_prefix('#') else { continue };
+        let tag: String = tag
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+            .collect::<String>()
+            .to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
     }
+    tags
 }
 
 // --- App State ---
@@ -61,11 +151,48 @@ struct App<'a> {
     virtual_time: DateTime<Local>,
     time_multiplier: f64,
     // Data State
+    archivist: Archivist,
+    pulses: mpsc::UnboundedSender<Pulse>,
     notes: HashMap<String, TimeNote>,
+    baseline: HashMap<String, TimeNote>, // The archive as last read from or written to disk
+    rings: Vec<rings::Ring>,
+    growth: rings::Growth,
+    index_generation: u64,
+    refit_pending: bool, // The window is fitted again once the regrown rings arrive
+    disk_busy: bool,    // The archivist is working on a job of ours
+    save_pending: bool, // A save waits for that job to be answered
+    save_failed: bool,
+    settled_drafts: Vec<String>, // Drafts to drop with the next successful save
+    external_change: bool,
     selected_minute: Option<u32>, // 0-59 for minute positions
+    pinned_hour: Option<u32>,     // Hour of a node picked on an outer ring; otherwise the virtual hour
     // UI State
     textarea: TextArea<'a>,
     is_editing: bool,
+    editing_key: Option<String>,
+    last_draft: Option<String>,
+    drafts: Option<HashMap<String, TimeNote>>, // Drafts offered back at launch
+    vault_key: Option<crypto::Key>,
+    prompt: Option<vault::ActivePrompt>,
+    status: Option<String>,
+    notice: Option<(String, time::Instant)>, // Passing word that fades, unlike the errors in `status`
+    arrangement: panels::Arrangement,
+    screen: Screen,
+    heatmap: heatmap::Heatmap,
+    almanac: Option<almanac::Almanac>, // Tallied while the almanac is open
+    resonance: Option<resonance::Resonance>,
+    alerts: alerts::Alerts,
+    breath: breath::Session,
+    canvas_area: Option<Rect>, // Where the astrolabe was last drawn, for mapping the mouse
+    gesture: Option<mouse::Gesture>,
+    last_click: Option<(time::Instant, u16, u16)>,
+    view: view::Viewport,
+    // Presence State
+    presence: presence::Ledger, // The ledger as the archivist last wrote it
+    unbanked: presence::Ledger, // Presence not yet handed to the archivist
+    ledger_busy: bool,
+    last_input: time::Instant,
+    sealed_nodes: usize,
     // Visual Effects State
     emanations: Vec<Emanation>,
     // Dendrochronology State
@@ -73,15 +200,23 @@ struct App<'a> {
     spin_velocity: f64,  // Momentum for the astrolabe spin
 }
 
+/// What fills the terminal besides the overlays.
+#[derive(Clone, Copy, PartialEq)]
+enum Screen {
+    Astrolabe,
+    Heatmap,
+    Almanac,
+    Breath,
+}
+
 struct Emanation {
     phase_offset: f64,
 }
 
 impl<'a> App<'a> {
-    fn new() -> Self {
+    fn new(notes: HashMap<String, TimeNote>, archivist: Archivist, pulses: mpsc::UnboundedSender<Pulse>) -> Self {
 
         let now = Local::now();
-        let notes = load_notes();
 
         let mut textarea = TextArea::default();
         textarea.set_block(
@@ -90,6 +225,7 @@ impl<'a> App<'a> {
             .title("Temporal Observation Node")
             .style(Style::default().fg(Color::Rgb(212, 175, 55))),
         );
+        let sealed_nodes = presence::sealed_nodes(&notes);
 
         Self {
             selected_ring_index: 0,
@@ -97,10 +233,46 @@ impl<'a> App<'a> {
             real_time_last_tick: now,
             virtual_time: now,
             time_multiplier: 1.0,
+            archivist,
+            pulses,
+            baseline: notes.clone(),
+            rings: rings::grow_rings(&notes, &rings::Growth::default(), now.date_naive()),
+            growth: rings::Growth::default(),
             notes,
+            index_generation: 0,
+            refit_pending: false,
+            disk_busy: false,
+            save_pending: false,
+            save_failed: false,
+            settled_drafts: Vec::new(),
+            external_change: false,
             selected_minute: None,
+            pinned_hour: None,
             textarea,
             is_editing: false,
+            editing_key: None,
+            last_draft: None,
+            drafts: None,
+            vault_key: None,
+            prompt: None,
+            status: None,
+            notice: None,
+            arrangement: panels::Arrangement::Normal,
+            screen: Screen::Astrolabe,
+            heatmap: heatmap::Heatmap::new(now.date_naive()),
+            almanac: None,
+            resonance: None,
+            alerts: alerts::Alerts::default(),
+            breath: breath::Session::default(),
+            canvas_area: None,
+            gesture: None,
+            last_click: None,
+            view: view::Viewport::default(),
+            presence: presence::Ledger::default(),
+            unbanked: presence::Ledger::default(),
+            ledger_busy: false,
+            last_input: time::Instant::now(),
+            sealed_nodes,
             emanations: vec![
                 Emanation { phase_offset: 0.0 },
             ],
@@ -115,8 +287,11 @@ impl<'a> App<'a> {
         let delta_micros = delta.num_microseconds().unwrap_or(0);
 
         let virtual_delta = Duration::microseconds((delta_micros as f64 * self.time_multiplier) as i64);
-        self.virtual_time = self.virtual_time + virtual_delta;
+        self.virtual_time += virtual_delta;
         self.real_time_last_tick = now;
+        self.attend(delta.to_std().unwrap_or_default());
+        self.listen_for_resonance();
+        self.tend_breath();
 
         // Apply friction to the astrolabe spin
         self.spin_angle += self.spin_velocity;
@@ -126,22 +301,61 @@ impl<'a> App<'a> {
         }
     }
 
-    fn get_breathing_scale(&self, phase_offset: f64) -> f64 {
-        let total_secs = self.virtual_time.timestamp() as f64 + self.virtual_time.nanosecond() as f64 / 1_000_000_000.0;
-        let t = (total_secs + phase_offset) % 13.0;
+    /// Regrows the rings from the current notes off the main loop; only the
+    /// newest generation is kept when several are in flight.
+    fn reindex(&mut self) {
+        self.index_generation += 1;
+        let generation = self.index_generation;
+        let notes = self.notes.clone();
+        let pulses = self.pulses.clone();
+        let growth = self.growth;
+        let today = self.virtual_time.date_naive();
+        tokio::task::spawn_blocking(move || {
+            let _ = pulses.send(Pulse::Indexed { generation, rings: rings::grow_rings(&notes, &growth, today) });
+        });
+    }
 
-        if t < 4.0 {
-            t / 4.0
-        } else if t < 5.0 {
-            1.0
-        } else {
-            1.0 - (t - 5.0) / 8.0
+    /// Lays the rings out anew, keeping the cursor on the ring holding the same date.
+    fn regrow(&mut self, growth: rings::Growth) {
+        self.growth = growth;
+        self.refit_pending = true;
+        if !self.save_failed {
+            self.status = None;
+        }
+        self.reindex();
+    }
+
+    /// Swaps in regrown rings, keeping the cursor on the ring of the same date.
+    fn on_indexed(&mut self, generation: u64, rings: Vec<rings::Ring>) {
+        if generation != self.index_generation {
+            return;
+        }
+        let selected_date = self.selected_ring_date();
+        self.rings = rings;
+        self.sealed_nodes = presence::sealed_nodes(&self.notes);
+        self.select_ring_date(selected_date);
+        if std::mem::take(&mut self.refit_pending) {
+            self.refit();
+        }
+        if self.almanac.is_some() {
+            self.almanac = Some(almanac::Almanac::tally(&self.notes, self.virtual_time.date_naive()));
         }
     }
 
+    fn get_breathing_scale(&self, phase_offset: f64) -> f64 {
+        // A guided session paces the breath everywhere, in real seconds
+        if let Some(elapsed) = self.breath.elapsed() {
+            return breath::breath_at(self.breath.phases(), elapsed + phase_offset).0;
+        }
+        let total_secs = self.virtual_time.timestamp() as f64 + self.virtual_time.nanosecond() as f64 / 1_000_000_000.0;
+        breath::breath_at(breath::GOLDEN, total_secs + phase_offset).0
+    }
+
     fn draw_shader_layer(&self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
-        let center_x = area.left() as f64 + area.width as f64 / 2.0;
-        let center_y = area.top() as f64 + area.height as f64 / 2.0;
+        // The glow follows the canvas window as it is zoomed and panned
+        let view = &self.view;
+        let center_x = area.left() as f64 + area.width as f64 * (0.5 - view.x / (2.0 * view.half_extent));
+        let center_y = area.top() as f64 + area.height as f64 * (0.5 + view.y / (2.0 * view.half_extent));
 
         let t = self.virtual_time;
         let sub_second = t.nanosecond() as f64 / 1_000_000_000.0;
@@ -151,7 +365,7 @@ impl<'a> App<'a> {
         let minute_angle = (90.0 - minute_val * 6.0).to_radians();
 
         let spirit_r = 95.0;
-        let clock_radius_screen_y = (area.height as f64 * 0.45).min(area.width as f64 * 0.22);
+        let clock_radius_screen_y = (area.height as f64 * 0.45).min(area.width as f64 * 0.22) * view.zoom();
         let clock_radius_screen_x = clock_radius_screen_y * 2.1;
 
         let total_secs = t.timestamp() as f64 + t.nanosecond() as f64 / 1_000_000_000.0;
@@ -227,139 +441,455 @@ impl<'a> App<'a> {
         }
     }
 
-    fn get_date_key(&self, minute_offset: u32) -> String {
-        let mut sorted_dates: Vec<String> = self.notes.keys()
-        .map(|k| k.split('-').take(3).collect::<Vec<&str>>().join("-"))
-        .collect::<std::collections::HashSet<_>>() // Unique dates
-        .into_iter()
-        .collect();
-        sorted_dates.sort();
-        sorted_dates.reverse();
+    /// The date of the outer ring under the cursor; `None` on the inner watch.
+    fn selected_ring_date(&self) -> Option<NaiveDate> {
+        let index = self.selected_ring_index.checked_sub(1)?;
+        self.rings.get(index).map(|ring| ring.date)
+    }
 
+    /// Puts the cursor back on the ring holding `date`, wherever that ring now lies.
+    fn select_ring_date(&mut self, date: Option<NaiveDate>) {
+        let Some(date) = date else { return };
+        match self.rings.iter().position(|ring| ring.contains(date)) {
+            Some(i) => self.selected_ring_index = i + 1,
+            None => self.selected_ring_index = self.selected_ring_index.min(self.rings.len()),
+        }
+    }
+
+    /// Puts the cursor on the node of `date` at `hour:minute`; `false` when no ring holds that date.
+    fn select_node(&mut self, date: NaiveDate, hour: u32, minute: u32) -> bool {
+        let Some(ring) = self.rings.iter().position(|ring| ring.contains(date)) else { return false };
+        self.selected_ring_index = ring + 1;
+        self.pinned_hour = Some(hour);
+        self.selected_minute = Some(minute);
+        self.keep_cursor_in_view();
+        true
+    }
+
+    /// The outer ring under the cursor; `None` on the inner watch.
+    fn selected_ring(&self) -> Option<&rings::Ring> {
+        self.rings.get(self.selected_ring_index.checked_sub(1)?)
+    }
+
+    /// The key of the node under the cursor. Week, month and year rings
+    /// gather many days, so no single node can be opened from them.
+    fn selected_key(&self) -> Option<String> {
+        let m = self.selected_minute?;
+        if self.selected_ring().is_some_and(|ring| ring.period != rings::Period::Day) {
+            return None;
+        }
+        Some(self.get_date_key(m))
+    }
+
+    /// The date the cursor's ring stands for; the inner watch is today.
+    fn selected_date(&self) -> NaiveDate {
         // If we are navigating an outer ring, use that date. Otherwise, use current date.
-        let date_part = if self.selected_ring_index > 0 && self.selected_ring_index <= sorted_dates.len() {
-            sorted_dates[self.selected_ring_index - 1].clone()
+        if self.selected_ring_index > 0 && self.selected_ring_index <= self.rings.len() {
+            self.rings[self.selected_ring_index - 1].date
         } else {
-            self.virtual_time.format("%Y-%m-%d").to_string()
+            self.virtual_time.date_naive()
+        }
+    }
+
+    /// The hour the cursor's minutes belong to; the inner watch always keeps the present hour.
+    fn selected_hour(&self) -> u32 {
+        match self.pinned_hour {
+            Some(h) if self.selected_ring_index > 0 => h,
+            _ => self.virtual_time.hour(),
+        }
+    }
+
+    /// Where the cursor sits on the astrolabe, in canvas units.
+    fn cursor_point(&self) -> Option<(f64, f64)> {
+        let m = self.selected_minute?;
+        let radius = self.selected_ring().map_or(rings::INNER_WATCH_RADIUS, |ring| ring.radius);
+        // A picked node keeps the cursor on its hour
+        let angle = match self.pinned_hour {
+            Some(h) if self.selected_ring_index > 0 => rings::node_angle_deg(h, m),
+            _ => rings::tick_angle_deg(m as f64),
         };
+        let rad = (angle + self.spin_angle.to_degrees()).to_radians();
+        Some((radius * rad.cos(), radius * rad.sin()))
+    }
 
-        format!("{}-{:02}-{:02}", date_part, self.virtual_time.hour(), minute_offset)
+    fn get_date_key(&self, minute_offset: u32) -> String {
+        note_key(self.selected_date(), self.selected_hour(), minute_offset)
     }
 }
 
 fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        return cli::run(&args);
+    }
+
+    // Open a plaintext archive before touching the terminal so a refusal reads
+    // cleanly; a sealed one has to wait for the unlock prompt.
+    let mut store = Store::new(SAVE_FILE);
+    let notes = if store.is_sealed()? { None } else { Some(store.load()?) };
+    let runtime = tokio::runtime::Runtime::new()?;
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new();
-    let tick_rate = time::Duration::from_millis(16);
-    let mut last_tick = Instant::now();
+    // A panic prints its message only after the terminal is usable again
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() == Some("main") {
+            restore_terminal();
+        }
+        default_hook(info);
+    }));
 
-    loop {
-        terminal.draw(|f| ui(f, &mut app))?;
-
-        let timeout = tick_rate
-        .checked_sub(last_tick.elapsed())
-        .unwrap_or_else(|| time::Duration::from_secs(0));
-
-        if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    if app.is_editing {
-                        match key.code {
-                            KeyCode::Esc => {
-                                app.is_editing = false;
-                                if let Some(m) = app.selected_minute {
-                                    let key = app.get_date_key(m);
-                                    let content = app.textarea.lines().join("\n");
-                                    app.notes.insert(key, TimeNote { content, is_locked: false });
-                                    save_notes(&app.notes);
-                                }
-                            }
-                            _ => {
-                                let ratatui_key = ratatui::crossterm::event::KeyEvent::from(key);
-                                app.textarea.input(ratatui_key);
-                            }
-                        }
-                    } else {
-                        match key.code {
-                            KeyCode::Char('q') | KeyCode::Char('Q') => app.should_quit = true,
-                            KeyCode::Char('+') => app.time_multiplier += 0.1,
-                            KeyCode::Char('-') => app.time_multiplier = (app.time_multiplier - 0.1).max(0.0),
-                            KeyCode::Char('[') => app.spin_velocity += 0.05,
-                            KeyCode::Char(']') => app.spin_velocity -= 0.05,
-                            // Angular Navigation (Minutes)
-                            KeyCode::Right => {
-                                let new_m = app.selected_minute.map(|m| (m + 1) % 60).unwrap_or(0);
-                                app.selected_minute = Some(new_m);
-                            }
-                            KeyCode::Left => {
-                                let new_m = app.selected_minute.map(|m| if m == 0 { 59 } else { m - 1 }).unwrap_or(0);
-                                app.selected_minute = Some(new_m);
-                            }
-
-                            // Radial Navigation (Rings/Dates)
-                            KeyCode::Up => {
-                                // Move to an outer ring
-                                app.selected_ring_index += 1;
-                            }
-                            KeyCode::Down => {
-                                // Move toward the center
-                                if app.selected_ring_index > 0 {
-                                    app.selected_ring_index -= 1;
-                                }
-                            }
-                            KeyCode::Enter => {
-                                if let Some(m) = app.selected_minute {
-                                    app.is_editing = true;
-                                    let key = app.get_date_key(m);
-                                    if let Some(note) = app.notes.get(&key) {
-                                        app.textarea = TextArea::from(note.content.lines());
-                                    } else {
-                                        app.textarea = TextArea::default();
-                                    }
-
-                                    let title = format!("Temporal Observation Node: Minute {:02}", m);
-                                    app.textarea.set_block(
-                                        Block::default()
-                                        .borders(Borders::ALL)
-                                        .title(title)
-                                        .style(Style::default().fg(Color::Rgb(212, 175, 55))),
-                                    );
-                                }
-                            }
-                            _ => {}
-                        }
+    let result = runtime.block_on(run(&mut terminal, store, notes));
+    restore_terminal();
+    result
+}
+
+/// Leaves raw mode, the alternate screen and mouse capture; harmless when repeated.
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, crossterm::cursor::Show);
+}
+
+type Term = Terminal<CrosstermBackend<io::Stdout>>;
+
+async fn run(terminal: &mut Term, mut store: Store, notes: Option<HashMap<String, TimeNote>>) -> Result<()> {
+    let notes = match notes {
+        Some(notes) => notes,
+        None => match unlock_archive(terminal, &mut store)? {
+            Some(notes) => notes,
+            None => return Ok(()),
+        },
+    };
+
+    let drafts = store.drafts().load();
+    let ledger = store.presence().load_value();
+    let (pulses, mut heart) = mpsc::unbounded_channel();
+    let archivist = Archivist::spawn(store, pulses.clone());
+    let mut app = App::new(notes, archivist, pulses.clone());
+    let size = terminal.size()?;
+    app.arrangement = panels::Arrangement::for_size(size.width, size.height);
+    app.refit();
+    match drafts {
+        Ok(drafts) => app.offer_drafts(drafts),
+        Err(e) => app.status = Some(format!("DRAFTS NOT READ: {:#}", e)),
+    }
+    match ledger {
+        Ok(ledger) => app.presence = ledger.unwrap_or_default(),
+        Err(e) => app.status = Some(format!("PRESENCE LEDGER NOT READ: {:#}", e)),
+    }
+    match alerts::Alerts::from_env() {
+        Ok(alerts) => app.alerts = alerts,
+        Err(e) => app.status = Some(format!("RESONANCE ALERTS OFF: {:#}", e)),
+    }
+
+    pulse::spawn_input(pulses.clone());
+    pulse::spawn_signals(pulses.clone())?;
+    pulse::every(time::Duration::from_millis(16), pulses.clone(), || Pulse::Tick);
+    // Notes appended by scripts or other instances grow into the rings live
+    pulse::every(time::Duration::from_secs(1), pulses.clone(), || Pulse::WatchDue);
+    pulse::every(time::Duration::from_secs(5), pulses.clone(), || Pulse::AutosaveDue);
+    if let Ok(url) = std::env::var("CHRONOS_SYNC_URL") {
+        pulse::every(time::Duration::from_secs(300), pulses.clone(), move || Pulse::SyncDue(url.clone()));
+    }
+    drop(pulses);
+
+    terminal.draw(|f| ui(f, &mut app))?;
+    let mut outcome = Ok(());
+    while let Some(pulse) = heart.recv().await {
+        match panic::catch_unwind(AssertUnwindSafe(|| beat(terminal, &mut app, &mut heart, pulse))) {
+            Ok(Ok(true)) => {}
+            Ok(Ok(false)) => break,
+            // The terminal is gone (or the instrument broke); keep the notes all the same
+            Ok(Err(e)) => {
+                outcome = Err(e);
+                break;
+            }
+            Err(_) => {
+                outcome = Err(anyhow::anyhow!("the instrument failed unexpectedly; unsaved notes were banked"));
+                break;
+            }
+        }
+    }
+    if outcome.is_err() {
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| app.rescue()));
+    }
+
+    // Let the archivist finish banking what was handed to it.
+    app.bank_presence(true);
+    let settle = async {
+        while app.disk_busy || app.save_pending || app.ledger_busy {
+            match heart.recv().await {
+                // Presence counted while the last of it was being banked
+                Some(Pulse::Disk(archivist::Reply::Banked { banked, result })) => {
+                    let banked_ok = result.is_ok();
+                    app.on_banked(banked, result);
+                    if banked_ok {
+                        app.bank_presence(true);
                     }
                 }
+                Some(Pulse::Disk(reply)) => app.on_disk(reply),
+                Some(_) => {}
+                None => break,
+            }
+        }
+    };
+    if tokio::time::timeout(time::Duration::from_secs(10), settle).await.is_err() {
+        outcome = outcome.and(Err(anyhow::anyhow!("the archive did not answer; the last changes may not be saved")));
+    }
+    app.alerts.restore_title();
+
+    outcome
+}
+
+/// Handles a pulse and everything that arrived meanwhile, then draws once.
+/// `false` once the instrument should close.
+fn beat(terminal: &mut Term, app: &mut App, heart: &mut mpsc::UnboundedReceiver<Pulse>, pulse: Pulse) -> Result<bool> {
+    on_pulse(app, pulse);
+    while let Ok(pulse) = heart.try_recv() {
+        on_pulse(app, pulse);
+    }
+    if app.should_quit {
+        return Ok(false);
+    }
+    terminal.draw(|f| ui(f, app))?;
+    Ok(true)
+}
+
+fn on_pulse(app: &mut App, pulse: Pulse) {
+    if matches!(pulse, Pulse::Input(_)) {
+        app.last_input = time::Instant::now();
+    }
+    match pulse {
+        Pulse::Input(Event::Key(key)) if key.kind == KeyEventKind::Press => app.following_cursor(|app| on_key(app, key)),
+        Pulse::Input(Event::Mouse(event)) if !app.is_modal() && app.screen == Screen::Astrolabe => app.following_cursor(|app| app.handle_mouse(event)),
+        Pulse::Input(Event::Resize(width, height)) => app.arrangement = panels::Arrangement::for_size(width, height),
+        Pulse::Input(_) => {}
+        Pulse::Tick => app.on_tick(),
+        Pulse::Disk(reply) => app.on_disk(reply),
+        Pulse::Indexed { generation, rings } => app.on_indexed(generation, rings),
+        Pulse::KeyDerived(derived) => app.resolve_prompt(derived),
+        Pulse::WatchDue => app.poll_archive(),
+        Pulse::AutosaveDue => {
+            app.autosave();
+            app.bank_presence(false);
+        }
+        Pulse::SyncDue(url) => app.sync_archive(url),
+        Pulse::Signal => {
+            app.rescue();
+            app.should_quit = true;
+        }
+    }
+}
+
+/// How long a notice stays in the footer.
+const NOTICE_SPAN: time::Duration = time::Duration::from_secs(4);
+
+impl App<'_> {
+    /// A vault, dialog or prompt holds the keyboard.
+    fn is_modal(&self) -> bool {
+        self.prompt.is_some() || self.external_change || self.drafts.is_some() || self.is_editing || self.resonance.is_some()
+    }
+
+    /// Tells of something that needs no fixing; it fades on its own, unlike `status`.
+    fn notify(&mut self, notice: impl Into<String>) {
+        self.notice = Some((notice.into(), time::Instant::now()));
+    }
+
+    /// The footer's word for the moment: the error in `status`, else a notice that has not faded yet.
+    fn message(&self) -> Option<Span<'static>> {
+        if let Some(status) = &self.status {
+            return Some(Span::styled(status.clone(), Style::default().fg(Color::Red)));
+        }
+        let (notice, _) = self.notice.as_ref().filter(|(_, at)| at.elapsed() < NOTICE_SPAN)?;
+        Some(Span::styled(notice.clone(), Style::default().fg(panels::AMBER)))
+    }
+}
+
+fn on_key(app: &mut App, key: KeyEvent) {
+    if app.prompt.is_some() {
+        app.handle_prompt_key(key);
+    } else if app.external_change {
+        app.handle_external_change_key(key);
+    } else if app.drafts.is_some() {
+        app.handle_drafts_key(key);
+    } else if app.is_editing {
+        match key.code {
+            KeyCode::Esc => app.close_vault(),
+            _ => {
+                app.textarea.input(key);
             }
         }
+    } else if app.resonance.is_some() {
+        app.handle_resonance_key(key);
+    } else if app.screen == Screen::Heatmap {
+        app.handle_heatmap_key(key);
+    } else if app.screen == Screen::Almanac {
+        app.handle_almanac_key(key);
+    } else if app.screen == Screen::Breath {
+        app.handle_breath_key(key);
+    } else {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Char('Q') => app.should_quit = true,
+            KeyCode::Char('+') => app.time_multiplier += 0.1,
+            KeyCode::Char('-') => app.time_multiplier = (app.time_multiplier - 0.1).max(0.0),
+            KeyCode::Char('[') => app.spin_velocity += 0.05,
+            KeyCode::Char(']') => app.spin_velocity -= 0.05,
+            KeyCode::Char('s') => app.toggle_seal(),
+            KeyCode::Char('S') => app.seal_with_passphrase(),
+            KeyCode::Char('h') => app.screen = Screen::Heatmap,
+            KeyCode::Char('a') => app.open_almanac(),
+            KeyCode::Char('r') => app.open_resonance(),
+            KeyCode::Char('m') => app.toggle_alerts(),
+            KeyCode::Char('b') => app.screen = Screen::Breath,
+            // Ring layouts
+            KeyCode::Char('g') => app.regrow(rings::Growth { layout: app.growth.layout.next(), ..app.growth }),
+            KeyCode::Char('G') => app.regrow(rings::Growth { layout: app.growth.layout.prev(), ..app.growth }),
+            KeyCode::Char('e') => app.regrow(rings::Growth { every_day: !app.growth.every_day, ..app.growth }),
+            KeyCode::Char('{') => app.regrow(rings::Growth {
+                layout: rings::RingLayout::Window,
+                window_days: app.growth.window_days.saturating_sub(7).max(7),
+                ..app.growth
+            }),
+            KeyCode::Char('}') => app.regrow(rings::Growth {
+                layout: rings::RingLayout::Window,
+                window_days: app.growth.window_days + 7,
+                ..app.growth
+            }),
+            // Zoom and pan the canvas
+            KeyCode::Char('z') => app.zoom(view::ZOOM_STEP),
+            KeyCode::Char('x') => app.zoom(1.0 / view::ZOOM_STEP),
+            KeyCode::Char('f') => app.fit_rings(),
+            KeyCode::Char('0') => app.view = view::Viewport::default(),
+            KeyCode::Char('H') => app.pan(-1.0, 0.0),
+            KeyCode::Char('L') => app.pan(1.0, 0.0),
+            KeyCode::Char('K') => app.pan(0.0, 1.0),
+            KeyCode::Char('J') => app.pan(0.0, -1.0),
+            // Angular Navigation (Minutes)
+            KeyCode::Right => {
+                let new_m = app.selected_minute.map(|m| (m + 1) % 60).unwrap_or(0);
+                app.selected_minute = Some(new_m);
+            }
+            KeyCode::Left => {
+                let new_m = app.selected_minute.map(|m| if m == 0 { 59 } else { m - 1 }).unwrap_or(0);
+                app.selected_minute = Some(new_m);
+            }
 
-        if last_tick.elapsed() >= tick_rate {
-            app.on_tick();
-            last_tick = Instant::now();
+            // Radial Navigation (Rings/Dates)
+            KeyCode::Up => {
+                // Move to an outer ring
+                app.selected_ring_index = (app.selected_ring_index + 1).min(app.rings.len());
+            }
+            KeyCode::Down => {
+                // Move toward the center
+                app.selected_ring_index = app.selected_ring_index.saturating_sub(1);
+            }
+            KeyCode::Enter => app.open_vault(),
+            _ => {}
         }
+    }
+}
 
-        if app.should_quit {
-            break;
+/// Asks for the archive passphrase until it opens the archive or the user leaves.
+fn unlock_archive(terminal: &mut Term, store: &mut Store) -> Result<Option<HashMap<String, TimeNote>>> {
+    let mut prompt = PassphrasePrompt::new("ARCHIVE SEALED: SPEAK THE PASSPHRASE");
+
+    loop {
+        terminal.draw(|f| draw_unlock(f, &prompt))?;
+
+        let Event::Key(key) = event::read()? else { continue };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match prompt.handle_key(key) {
+            PromptAction::Pending => {}
+            PromptAction::Cancel => return Ok(None),
+            PromptAction::Submit(passphrase) => {
+                prompt.busy = true;
+                terminal.draw(|f| draw_unlock(f, &prompt))?;
+                prompt.busy = false;
+
+                match store.unlock(&passphrase) {
+                    Ok(()) => return store.load().map(Some),
+                    Err(e) => prompt.error = Some(format!("{:#}", e)),
+                }
+            }
         }
     }
+}
 
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-             LeaveAlternateScreen,
-             DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+fn draw_unlock(f: &mut Frame, prompt: &PassphrasePrompt) {
+    let title = Paragraph::new("* CHRONOS PLANTACERIUM *\nAETERNUM PRECISION ARCHIVE")
+    .style(Style::default().fg(Color::Rgb(212, 175, 55)).add_modifier(Modifier::BOLD))
+    .alignment(ratatui::layout::Alignment::Center);
+    let area = f.area();
+    f.render_widget(title, Rect { height: area.height.min(3), ..area }.inner(ratatui::layout::Margin::new(0, 1)));
 
-    Ok(())
+    prompt.render(f, centered_box(60, 7, area));
 }
 
 fn ui(f: &mut Frame, app: &mut App) {
+    app.canvas_area = if app.screen == Screen::Heatmap {
+        heatmap::draw_heatmap(f, app);
+        None
+    } else if app.screen == Screen::Almanac {
+        almanac::draw_almanac(f, app);
+        None
+    } else if app.screen == Screen::Breath {
+        breath::draw_breath(f, app);
+        None
+    } else if app.arrangement == panels::Arrangement::Compact {
+        draw_compact(f, app);
+        None
+    } else {
+        Some(draw_full(f, app))
+    };
+
+    if app.is_editing {
+        let area = centered_rect(70, 60, f.area());
+        f.render_widget(ratatui::widgets::Clear, area);
+
+        let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(" TEMPORAL OBSERVATION VAULT ", Style::default().fg(Color::Rgb(212, 175, 55)).add_modifier(Modifier::BOLD)))
+        .title_bottom(Line::from(" [ESC] TO LOCK NODE (SAVE INTERFACE) ").alignment(ratatui::layout::Alignment::Right));
+
+        app.textarea.set_block(block);
+        let revisions = app.editing_key.as_ref().and_then(|k| app.notes.get(k)).map(|n| n.revisions.as_slice()).unwrap_or(&[]);
+        if revisions.is_empty() {
+            f.render_widget(&app.textarea, area);
+        } else {
+            let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length((revisions.len() as u16 * 2 + 2).min(area.height / 2))])
+            .split(area);
+            f.render_widget(&app.textarea, split[0]);
+            vault::draw_revisions(f, split[1], revisions);
+        }
+    }
+
+    if app.resonance.is_some() {
+        resonance::draw_resonance(f, centered_rect(70, 60, f.area()), app);
+    }
+
+    if app.external_change {
+        reconcile::draw_external_change(f, centered_box(90, 7, f.area()));
+    } else if let Some(drafts) = &app.drafts {
+        drafts::draw_drafts(f, centered_box(90, 10, f.area()), drafts);
+    }
+
+    if let Some(active) = &app.prompt {
+        active.prompt.render(f, centered_box(60, 7, f.area()));
+    }
+}
+
+/// Title, astrolabe and footer; on wide terminals the rings and the node flank the canvas.
+/// Returns where the astrolabe was drawn.
+fn draw_full(f: &mut Frame, app: &App) -> Rect {
     let chunks = Layout::default()
     .direction(Direction::Vertical)
     .margin(1)
@@ -380,14 +910,72 @@ fn ui(f: &mut Frame, app: &mut App) {
     .block(Block::default().borders(Borders::NONE));
     f.render_widget(title, chunks[0]);
 
-    let canvas_area = chunks[1];
+    let canvas_area = if app.arrangement == panels::Arrangement::Wide {
+        let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Length(32), Constraint::Min(40), Constraint::Length(42)])
+        .split(chunks[1]);
+        panels::draw_ring_list(f, columns[0], app);
+        panels::draw_node_detail(f, columns[2], app);
+        columns[1]
+    } else {
+        chunks[1]
+    };
+    draw_astrolabe(f, canvas_area, app);
+
+    let experience_units = app.ledger().experience_units(app.sealed_nodes);
+    let mut status_line = vec![
+        Span::raw("SPEED: "),
+               Span::styled(format!("{:.1}x", app.time_multiplier), Style::default().fg(if app.time_multiplier > 1.0 { Color::Red } else { Color::Green })),
+               Span::raw(" | "),
+               Span::styled("EXPERIENCE UNITS: ", Style::default().fg(Color::DarkGray)),
+               Span::styled(format!("{}", experience_units), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+               Span::raw(" | "),
+               Span::styled("RINGS: ", Style::default().fg(Color::DarkGray)),
+               Span::styled(app.growth.describe(), Style::default().fg(Color::Rgb(212, 175, 55))),
+    ];
+    if let Some(message) = app.message() {
+        status_line.push(Span::raw(" | "));
+        status_line.push(message);
+    }
+    let stats_text = vec![
+        Line::from(status_line),
+        Line::from(vec![
+            Span::raw("CONTROLS: Arrow Keys (Nav) | [ / ] (Spin Astrolabe) | z/x/f (Zoom) | HJKL (Pan) | g/G { } e (Rings) | h (Heatmap) | a (Almanac) | r (Resonance) | m (Mute) | b (Breathe) | Enter (Edit) | s/S (Seal) | +/- (Time) | Q (Quit)"),
+        ]),
+    ];
+    let footer = Paragraph::new(stats_text)
+    .alignment(ratatui::layout::Alignment::Left)
+    .block(Block::default().borders(Borders::TOP));
+    f.render_widget(footer, chunks[2]);
+    canvas_area
+}
+
+/// Lists and a tiny clock, with a single line of footer.
+fn draw_compact(f: &mut Frame, app: &App) {
+    let rows = Layout::default()
+    .direction(Direction::Vertical)
+    .constraints([Constraint::Min(8), Constraint::Length(1)])
+    .split(f.area());
+
+    panels::draw_compact(f, rows[0], app);
+
+    let footer = match app.message() {
+        Some(message) => Line::from(message),
+        None => Line::from(Span::styled("↑↓ ring  ←→ minute  ⏎ open  s/S seal  q quit", Style::default().fg(Color::DarkGray))),
+    };
+    f.render_widget(Paragraph::new(footer), rows[1]);
+}
+
+/// The astrolabe: shader glow, tree rings, the inner watch and its hands.
+fn draw_astrolabe(f: &mut Frame, canvas_area: Rect, app: &App) {
     app.draw_shader_layer(canvas_area, f.buffer_mut());
 
     let canvas = Canvas::default()
     .block(Block::default().borders(Borders::NONE))
-    // Expanded boundaries to allow room for the outer Dendrochronology rings
-    .x_bounds([-250.0, 250.0])
-    .y_bounds([-250.0, 250.0])
+    // The window onto the astrolabe, zoomed and panned to reach the outer Dendrochronology rings
+    .x_bounds(app.view.x_bounds())
+    .y_bounds(app.view.y_bounds())
     .marker(ratatui::symbols::Marker::Dot)
     .paint(|ctx| {
         let gold = Color::Rgb(212, 175, 55);
@@ -395,69 +983,72 @@ fn ui(f: &mut Frame, app: &mut App) {
         let active_hand = Color::Rgb(252, 246, 186);
 
         // --- DENDROCHRONOLOGY: TREE RINGS OF TIME ---
-        // Parse keys into grouped dates
-        let mut grouped_notes: HashMap<String, Vec<(u32, u32)>> = HashMap::new();
-        for key in app.notes.keys() {
-            let parts: Vec<&str> = key.split('-').collect();
-            if parts.len() == 5 {
-                let date = format!("{}-{}-{}", parts[0], parts[1], parts[2]);
-                if let (Ok(h), Ok(m)) = (parts[3].parse::<u32>(), parts[4].parse::<u32>()) {
-                    grouped_notes.entry(date).or_default().push((h, m));
-                }
-            }
-        }
-
-        let mut sorted_dates: Vec<String> = grouped_notes.keys().cloned().collect();
-        sorted_dates.sort();
-        sorted_dates.reverse(); // Inner rings are the newest dates
-
-        let base_radius = 140.0;
-        let ring_spacing = 30.0;
-
-        for (i, date) in sorted_dates.iter().enumerate() {
-            let radius = base_radius + (i as f64 * ring_spacing);
+        // The fullest minute of any gathered ring sets the scale for all of them
+        let peak = app.rings.iter()
+        .filter(|ring| ring.period != rings::Period::Day)
+        .flat_map(|ring| ring.times.values().copied())
+        .max()
+        .unwrap_or(1);
+        // And the fullest ring sets how thick the others grow when droughts are shown
+        let fullest = app.rings.iter().map(|ring| ring.notes()).max().unwrap_or(1);
+
+        for ring in &app.rings {
+            let radius = ring.radius;
+            let gathered = ring.period != rings::Period::Day;
 
             // Draw the faint temporal ring
-            ctx.draw(&Circle { x: 0.0, y: 0.0, radius, color: Color::Rgb(40, 40, 40) });
-
-            // Draw the fossilized nodes (Amber Encapsulation)
-            if let Some(times) = grouped_notes.get(date) {
-                for &(h, m) in times {
-                    // Minute precision mapping (1440 mins in a day -> 360 degrees)
-                    let total_minutes = (h * 60 + m) as f64;
-                    let base_angle_deg = 90.0 - ((total_minutes / 1440.0) * 360.0);
-                    let final_angle_deg = base_angle_deg + app.spin_angle.to_degrees();
-                    let rad = final_angle_deg.to_radians();
-
-                    let nx = radius * rad.cos();
-                    let ny = radius * rad.sin();
-
-                    let is_resonant = app.virtual_time.hour() == h && app.virtual_time.minute() == m;
-
-                    if is_resonant {
-                        // Bioluminescent Resonance: Connects the past to the present core
-                        ctx.draw(&CanvasLine { x1: 0.0, y1: 0.0, x2: nx, y2: ny, color: Color::Rgb(150, 150, 50) });
-                        ctx.draw(&Circle { x: nx, y: ny, radius: 4.0, color: Color::Yellow });
-                    } else {
-                        // Standard Amber Node
-                        ctx.draw(&Circle { x: nx, y: ny, radius: 2.0, color: Color::Rgb(255, 191, 0) });
-                    }
+            if !app.growth.every_day {
+                ctx.draw(&Circle { x: 0.0, y: 0.0, radius, color: Color::Rgb(40, 40, 40) });
+            } else if ring.times.is_empty() {
+                // Drought: a thin ring, barely there
+                ctx.draw(&Circle { x: 0.0, y: 0.0, radius, color: Color::Rgb(22, 22, 22) });
+            } else {
+                // Plenty: the ring thickens with the notes it holds
+                let layers = 1 + (3.0 * rings::density(ring.notes(), fullest)).round() as i32;
+                for layer in 0..layers {
+                    let offset = (layer - layers / 2) as f64 * 1.5;
+                    ctx.draw(&Circle { x: 0.0, y: 0.0, radius: radius + offset, color: Color::Rgb(55, 48, 30) });
                 }
             }
-            let radius = if app.selected_ring_index == 0 {
-                100.0 // Inner Watch
-            } else {
-                base_radius + ((app.selected_ring_index - 1) as f64 * ring_spacing)
-            };
-
-            if let Some(m) = app.selected_minute {
-                // Calculate angle for the minute
-                let angle_deg = 90.0 - (m as f64 * 6.0) + app.spin_angle.to_degrees();
-                let rad = angle_deg.to_radians();
 
-                let cx = radius * rad.cos();
-                let cy = radius * rad.sin();
+            // Draw the fossilized nodes (Amber Encapsulation)
+            for (&(h, m), &count) in &ring.times {
+                let final_angle_deg = rings::node_angle_deg(h, m) + app.spin_angle.to_degrees();
+                let rad = final_angle_deg.to_radians();
+
+                let nx = radius * rad.cos();
+                let ny = radius * rad.sin();
+
+                let is_resonant = app.virtual_time.hour() == h && app.virtual_time.minute() == m;
+                let note = if gathered { None } else { app.notes.get(&note_key(ring.date, h, m)) };
+
+                if is_resonant {
+                    // Bioluminescent Resonance: Connects the past to the present core
+                    ctx.draw(&CanvasLine { x1: 0.0, y1: 0.0, x2: nx, y2: ny, color: Color::Rgb(150, 150, 50) });
+                    ctx.draw(&Circle { x: nx, y: ny, radius: 4.0, color: Color::Yellow });
+                } else if gathered {
+                    // Gathered Node: the more notes it holds, the larger and brighter the resin
+                    let density = rings::density(count, peak);
+                    let color = Color::Rgb(
+                        (110.0 + 145.0 * density) as u8,
+                        (80.0 + 150.0 * density) as u8,
+                        (60.0 * density) as u8,
+                    );
+                    ctx.draw(&Circle { x: nx, y: ny, radius: 1.0 + 3.0 * density, color });
+                } else if note.is_some_and(|n| n.is_encrypted()) {
+                    // Passphrase-Sealed Node: resin clouded to a deep garnet
+                    ctx.draw(&Circle { x: nx, y: ny, radius: 2.0, color: Color::Rgb(178, 34, 34) });
+                } else {
+                    // Standard Amber Node
+                    ctx.draw(&Circle { x: nx, y: ny, radius: 2.0, color: Color::Rgb(255, 191, 0) });
+                }
 
+                if note.is_some_and(|n| n.is_locked) {
+                    // Sealed nodes wear a gilded band
+                    ctx.draw(&Circle { x: nx, y: ny, radius: 3.5, color: gold });
+                }
+            }
+            if let Some((cx, cy)) = app.cursor_point() {
                 // Draw a pulsing white cursor on the selected ring/minute
                 ctx.draw(&Circle {
                     x: cx,
@@ -511,7 +1102,7 @@ fn ui(f: &mut Frame, app: &mut App) {
             let side_offset = 6.0_f64.to_radians();
 
             let r_base = 102.0;
-            let r_apex = 118.0;
+            let r_apex = rings::PETAL_RADIUS;
 
             let x_apex = r_apex * rad.cos();
             let y_apex = r_apex * rad.sin();
@@ -569,37 +1160,6 @@ fn ui(f: &mut Frame, app: &mut App) {
         ctx.draw(&Circle { x: 0.0, y: 0.0, radius: 1.0, color: Color::White });
     });
     f.render_widget(canvas, canvas_area);
-
-    let experience_seconds = app.virtual_time.num_seconds_from_midnight();
-    let stats_text = vec![
-        Line::from(vec![
-            Span::raw("SPEED: "),
-                   Span::styled(format!("{:.1}x", app.time_multiplier), Style::default().fg(if app.time_multiplier > 1.0 { Color::Red } else { Color::Green })),
-                   Span::raw(" | "),
-                   Span::styled("EXPERIENCE UNITS: ", Style::default().fg(Color::DarkGray)),
-                   Span::styled(format!("{}", experience_seconds), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-        ]),
-        Line::from(vec![
-            Span::raw("CONTROLS: Arrow Keys (Nav) | [ / ] (Spin Astrolabe) | Enter (Edit) | +/- (Time) | Q (Quit)"),
-        ]),
-    ];
-    let footer = Paragraph::new(stats_text)
-    .alignment(ratatui::layout::Alignment::Left)
-    .block(Block::default().borders(Borders::TOP));
-    f.render_widget(footer, chunks[2]);
-
-    if app.is_editing {
-        let area = centered_rect(70, 60, f.area());
-        f.render_widget(ratatui::widgets::Clear, area);
-
-        let block = Block::default()
-        .borders(Borders::ALL)
-        .title(Span::styled(" TEMPORAL OBSERVATION VAULT ", Style::default().fg(Color::Rgb(212, 175, 55)).add_modifier(Modifier::BOLD)))
-        .title_bottom(Line::from(" [ESC] TO LOCK NODE (SAVE INTERFACE) ").alignment(ratatui::layout::Alignment::Right));
-
-        app.textarea.set_block(block);
-        f.render_widget(&app.textarea, area);
-    }
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -621,3 +1181,15 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     ])
     .split(popup_layout[1])[1]
 }
+
+/// A fixed-size box in the middle of `r`, shrunk to fit when `r` is smaller.
+fn centered_box(width: u16, height: u16, r: Rect) -> Rect {
+    let width = width.min(r.width);
+    let height = height.min(r.height);
+    Rect {
+        x: r.x + (r.width - width) / 2,
+        y: r.y + (r.height - height) / 2,
+        width,
+        height,
+    }
+}
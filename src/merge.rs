@@ -0,0 +1,190 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{Revision, TimeNote};
+
+// --- Confluence of Archives ---
+// Reconciles two note maps that grew apart. With a common ancestor, a side
+// that left a minute as the ancestor had it yields to the side that changed
+// it. Without one (or when both changed it), the newer note wins and the
+// other version is kept beside it as a revision, so nothing is lost.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Side {
+    Ours,
+    Theirs,
+}
+
+pub struct Conflict {
+    pub key: String,
+    /// The version that stayed current; the other became a revision.
+    pub kept: Side,
+}
+
+#[derive(Default)]
+pub struct Merged {
+    pub notes: HashMap<String, TimeNote>,
+    pub conflicts: Vec<Conflict>,
+    /// Minutes taken from their side without conflict.
+    pub taken: usize,
+}
+
+/// Merges `theirs` into `ours`; `source` names their archive in any revisions it leaves.
+pub fn merge(
+    base: Option<&HashMap<String, TimeNote>>,
+    ours: &HashMap<String, TimeNote>,
+    theirs: &HashMap<String, TimeNote>,
+    source: &str,
+) -> Merged {
+    let keys: BTreeSet<&String> = ours.keys().chain(theirs.keys()).collect();
+
+    let mut merged = Merged::default();
+    for key in keys {
+        let ancestor = base.map(|base| base.get(key));
+        let unchanged = |note: &TimeNote| match ancestor {
+            Some(Some(b)) => note.same_substance(b),
+            _ => false,
+        };
+
+        let kept = match (ours.get(key), theirs.get(key)) {
+            (Some(o), Some(t)) if o.same_substance(t) => Some(join_revisions(newer(o, t).clone(), o, t)),
+            (Some(o), Some(t)) if unchanged(t) => Some(join_revisions(o.clone(), o, t)),
+            (Some(o), Some(t)) if unchanged(o) => {
+                merged.taken += 1;
+                Some(join_revisions(t.clone(), o, t))
+            }
+            (Some(o), Some(t)) => {
+                let kept = if std::ptr::eq(newer(o, t), o) { Side::Ours } else { Side::Theirs };
+                merged.conflicts.push(Conflict { key: key.clone(), kept });
+                Some(match kept {
+                    Side::Ours => set_aside(join_revisions(o.clone(), o, t), t, source),
+                    Side::Theirs => set_aside(join_revisions(t.clone(), o, t), o, "here"),
+                })
+            }
+            // Present on one side only: deleted on the other when the ancestor had it unchanged
+            (Some(o), None) => (!unchanged(o)).then(|| o.clone()),
+            (None, Some(t)) if unchanged(t) => None,
+            (None, Some(t)) => {
+                merged.taken += 1;
+                Some(t.clone())
+            }
+            (None, None) => None,
+        };
+
+        if let Some(note) = kept {
+            merged.notes.insert(key.clone(), note);
+        }
+    }
+
+    merged
+}
+
+/// The more recently modified note; ties and unknown times favour `ours`.
+fn newer<'a>(ours: &'a TimeNote, theirs: &'a TimeNote) -> &'a TimeNote {
+    if theirs.modified > ours.modified { theirs } else { ours }
+}
+
+/// Carries the revisions of both sides over to the note that stays.
+fn join_revisions(mut kept: TimeNote, ours: &TimeNote, theirs: &TimeNote) -> TimeNote {
+    for revision in ours.revisions.iter().chain(&theirs.revisions) {
+        add_revision(&mut kept, revision.clone());
+    }
+    kept
+}
+
+fn set_aside(mut kept: TimeNote, other: &TimeNote, source: &str) -> TimeNote {
+    let revision = Revision {
+        content: other.content.clone(),
+        sealed: other.sealed.clone(),
+        modified: other.modified,
+        source: source.to_string(),
+    };
+    add_revision(&mut kept, revision);
+    kept
+}
+
+/// Keeps one copy of each version, whichever archive reported it first.
+fn add_revision(kept: &mut TimeNote, revision: Revision) {
+    let known = kept
+        .revisions
+        .iter()
+        .any(|r| r.content == revision.content && r.sealed == revision.sealed && r.modified == revision.modified);
+    if !known {
+        kept.revisions.push(revision);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    const KEY: &str = "2024-03-01-09-30";
+
+    /// A note last modified `hour` o'clock on the day of `KEY`.
+    fn note(content: &str, hour: u32) -> TimeNote {
+        let modified = Utc.with_ymd_and_hms(2024, 3, 1, hour, 0, 0).unwrap();
+        TimeNote { modified: Some(modified), ..TimeNote::new(content.to_string()) }
+    }
+
+    fn archive(notes: &[(&str, TimeNote)]) -> HashMap<String, TimeNote> {
+        notes.iter().map(|(key, note)| (key.to_string(), note.clone())).collect()
+    }
+
+    #[test]
+    fn deletion_on_one_side_wins_over_an_unchanged_other() {
+        let base = archive(&[(KEY, note("dawn", 9))]);
+        let unchanged = archive(&[(KEY, note("dawn", 9))]);
+
+        let merged = merge(Some(&base), &HashMap::new(), &unchanged, "other");
+        assert!(merged.notes.is_empty());
+        let merged = merge(Some(&base), &unchanged, &HashMap::new(), "other");
+        assert!(merged.notes.is_empty());
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn edits_on_both_sides_keep_the_newer_and_a_revision() {
+        let base = archive(&[(KEY, note("dawn", 9))]);
+        let ours = archive(&[(KEY, note("dawn, rain", 10))]);
+        let theirs = archive(&[(KEY, note("dawn, wind", 11))]);
+
+        let merged = merge(Some(&base), &ours, &theirs, "other");
+        let kept = &merged.notes[KEY];
+        assert_eq!(kept.content, "dawn, wind");
+        assert_eq!(kept.revisions.len(), 1);
+        assert_eq!(kept.revisions[0].content, "dawn, rain");
+        assert_eq!(kept.revisions[0].source, "here");
+        assert_eq!(merged.conflicts.len(), 1);
+        assert_eq!(merged.conflicts[0].kept, Side::Theirs);
+    }
+
+    #[test]
+    fn without_an_ancestor_nothing_is_deleted_and_the_newer_wins() {
+        let ours = archive(&[(KEY, note("dawn", 12)), ("2024-03-02-08-00", note("ours only", 8))]);
+        let theirs = archive(&[(KEY, note("dusk", 10)), ("2024-03-03-08-00", note("theirs only", 8))]);
+
+        let merged = merge(None, &ours, &theirs, "other");
+        assert_eq!(merged.notes.len(), 3);
+        assert_eq!(merged.taken, 1);
+        let kept = &merged.notes[KEY];
+        assert_eq!(kept.content, "dawn");
+        assert_eq!(kept.revisions[0].content, "dusk");
+        assert_eq!(kept.revisions[0].source, "other");
+        assert_eq!(merged.conflicts[0].kept, Side::Ours);
+    }
+
+    #[test]
+    fn identical_edits_on_both_sides_are_no_conflict() {
+        let base = archive(&[(KEY, note("dawn", 9))]);
+        let ours = archive(&[(KEY, note("dawn, rain", 10))]);
+        let theirs = archive(&[(KEY, note("dawn, rain", 11))]);
+
+        let merged = merge(Some(&base), &ours, &theirs, "other");
+        let kept = &merged.notes[KEY];
+        assert_eq!(kept.content, "dawn, rain");
+        assert_eq!(kept.modified, theirs[KEY].modified);
+        assert!(kept.revisions.is_empty());
+        assert!(merged.conflicts.is_empty());
+        assert_eq!(merged.taken, 0);
+    }
+}
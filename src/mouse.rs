@@ -0,0 +1,185 @@
+use ratatui::{
+    crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
+    layout::Rect,
+};
+use std::time::{Duration, Instant};
+
+use crate::{rings, view, App};
+
+// --- Mouse ---
+// Terminal cells are mapped back through the canvas bounds into astrolabe
+// space, where they are matched against the same ring geometry the canvas
+// is painted from. A click picks, a double-click opens the vault, the wheel
+// walks the rings and a drag turns the wheel of history.
+
+/// Two clicks on the same cell within this window open the vault.
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+/// How close (in cells) a click must land to a node or tick to pick it.
+const PICK_RADIUS: f64 = 1.5;
+/// The frame period the spin velocity is measured in.
+const FRAME: Duration = Duration::from_millis(16);
+
+/// A press on the canvas that may still become a drag.
+pub struct Gesture {
+    column: u16,
+    row: u16,
+    angle: f64,
+    at: Instant,
+    moved: bool,
+    velocity: f64,
+}
+
+enum Pick {
+    Tick(u32),
+    Node { ring: usize, hour: u32, minute: u32 },
+    Ring(usize),
+}
+
+impl App<'_> {
+    pub fn handle_mouse(&mut self, event: MouseEvent) {
+        match event.kind {
+            // With Ctrl held the wheel zooms instead
+            MouseEventKind::ScrollUp if event.modifiers.contains(KeyModifiers::CONTROL) => self.zoom(view::ZOOM_STEP),
+            MouseEventKind::ScrollDown if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.zoom(1.0 / view::ZOOM_STEP)
+            }
+            MouseEventKind::ScrollUp => self.selected_ring_index = (self.selected_ring_index + 1).min(self.rings.len()),
+            MouseEventKind::ScrollDown => self.selected_ring_index = self.selected_ring_index.saturating_sub(1),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some((x, y)) = self.canvas_point(event.column, event.row) else { return };
+                self.gesture = Some(Gesture {
+                    column: event.column,
+                    row: event.row,
+                    angle: y.atan2(x),
+                    at: Instant::now(),
+                    moved: false,
+                    velocity: 0.0,
+                });
+                // The wheel stops under the hand
+                self.spin_velocity = 0.0;
+            }
+            MouseEventKind::Drag(MouseButton::Left) => self.drag(event.column, event.row),
+            MouseEventKind::Up(MouseButton::Left) => {
+                let Some(gesture) = self.gesture.take() else { return };
+                if gesture.moved {
+                    // Let go, the astrolabe keeps turning until friction settles it
+                    self.spin_velocity = gesture.velocity;
+                } else {
+                    self.click(gesture.column, gesture.row);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn drag(&mut self, column: u16, row: u16) {
+        let Some((x, y)) = self.canvas_point(column, row) else { return };
+        let Some(gesture) = self.gesture.as_mut() else { return };
+        if !gesture.moved && (gesture.column, gesture.row) == (column, row) {
+            return;
+        }
+
+        let angle = y.atan2(x);
+        let mut delta = angle - gesture.angle;
+        // Crossing the west axis jumps a whole turn
+        if delta > std::f64::consts::PI {
+            delta -= std::f64::consts::TAU;
+        } else if delta < -std::f64::consts::PI {
+            delta += std::f64::consts::TAU;
+        }
+
+        let now = Instant::now();
+        let frames = (now - gesture.at).as_secs_f64() / FRAME.as_secs_f64();
+        gesture.velocity = (delta / frames.max(1.0)).clamp(-0.5, 0.5);
+        gesture.angle = angle;
+        gesture.at = now;
+        gesture.moved = true;
+        self.spin_angle += delta;
+    }
+
+    fn click(&mut self, column: u16, row: u16) {
+        let now = Instant::now();
+        let double = self
+            .last_click
+            .is_some_and(|(at, c, r)| (c, r) == (column, row) && now - at < DOUBLE_CLICK);
+        self.last_click = if double { None } else { Some((now, column, row)) };
+
+        match self.pick(column, row) {
+            Some(Pick::Tick(minute)) => {
+                self.selected_ring_index = 0;
+                self.pinned_hour = None;
+                self.selected_minute = Some(minute);
+            }
+            Some(Pick::Node { ring, hour, minute }) => {
+                self.selected_ring_index = ring + 1;
+                self.pinned_hour = Some(hour);
+                self.selected_minute = Some(minute);
+            }
+            Some(Pick::Ring(ring)) => self.selected_ring_index = ring + 1,
+            None => return,
+        }
+        if double {
+            self.open_vault();
+        }
+    }
+
+    /// The node or tick nearest to the cell, else the ring it lies on.
+    fn pick(&self, column: u16, row: u16) -> Option<Pick> {
+        let (x, y) = self.canvas_point(column, row)?;
+        let (cell_w, cell_h) = self.canvas_cell()?;
+        let cells = |px: f64, py: f64| (((px - x) / cell_w).powi(2) + ((py - y) / cell_h).powi(2)).sqrt();
+
+        let mut best: Option<(f64, Pick)> = None;
+        let mut consider = |distance: f64, pick: Pick| {
+            if distance <= PICK_RADIUS && best.as_ref().is_none_or(|(d, _)| distance < *d) {
+                best = Some((distance, pick));
+            }
+        };
+
+        for minute in 0..60 {
+            let rad = rings::tick_angle_deg(minute as f64).to_radians();
+            let (tx, ty) = (rings::INNER_WATCH_RADIUS * rad.cos(), rings::INNER_WATCH_RADIUS * rad.sin());
+            consider(cells(tx, ty), Pick::Tick(minute));
+        }
+        for (i, ring) in self.rings.iter().enumerate() {
+            for &(hour, minute) in ring.times.keys() {
+                let rad = (rings::node_angle_deg(hour, minute) + self.spin_angle.to_degrees()).to_radians();
+                consider(cells(ring.radius * rad.cos(), ring.radius * rad.sin()), Pick::Node { ring: i, hour, minute });
+            }
+        }
+        if let Some((_, pick)) = best {
+            return Some(pick);
+        }
+
+        let r = x.hypot(y);
+        self.rings
+            .iter()
+            .enumerate()
+            .map(|(i, ring)| (i, (ring.radius - r).abs()))
+            .filter(|&(_, off)| off <= rings::RING_SPACING / 2.0)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| Pick::Ring(i))
+    }
+
+    /// The astrolabe coordinates at the centre of a terminal cell, if it lies on the canvas.
+    fn canvas_point(&self, column: u16, row: u16) -> Option<(f64, f64)> {
+        let area = self.canvas_area?;
+        if !area.contains((column, row).into()) {
+            return None;
+        }
+        let (cell_w, cell_h) = self.canvas_cell()?;
+        let x = self.view.x_bounds()[0] + (column - area.x) as f64 * cell_w;
+        let y = self.view.y_bounds()[1] - (row - area.y) as f64 * cell_h;
+        Some((x, y))
+    }
+
+    /// Width and height of one cell in astrolabe units, as the canvas grid spaces them.
+    fn canvas_cell(&self) -> Option<(f64, f64)> {
+        let Rect { width, height, .. } = self.canvas_area?;
+        if width < 2 || height < 2 {
+            return None;
+        }
+        let span = 2.0 * self.view.half_extent;
+        Some((span / (width - 1) as f64, span / (height - 1) as f64))
+    }
+}
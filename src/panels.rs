@@ -0,0 +1,205 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    extract_tags, parse_note_key,
+    rings::{self, Period},
+    App,
+};
+
+// --- Responsive Panels ---
+// The astrolabe needs room to breathe. On narrow terminals the instrument
+// folds into lists and a tiny clock; on wide ones the rings and the node
+// under the cursor get panels beside the canvas.
+
+// The instrument's palette, shared by every screen drawn around the canvas.
+pub const GOLD: Color = Color::Rgb(212, 175, 55);
+pub const GOLD_DIM: Color = Color::Rgb(100, 80, 20);
+pub const AMBER: Color = Color::Rgb(255, 191, 0);
+pub const PALE: Color = Color::Rgb(252, 246, 186);
+pub const GARNET: Color = Color::Rgb(178, 34, 34);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Arrangement {
+    /// Ring list, minute strip and a tiny clock instead of the canvas.
+    Compact,
+    Normal,
+    /// The canvas flanked by the ring list and the selected node.
+    Wide,
+}
+
+impl Arrangement {
+    pub fn for_size(width: u16, height: u16) -> Self {
+        if width < 80 || height < 24 {
+            Arrangement::Compact
+        } else if width >= 150 && height >= 30 {
+            Arrangement::Wide
+        } else {
+            Arrangement::Normal
+        }
+    }
+}
+
+/// One line: the virtual time and a glyph that swells and settles with the breath.
+pub fn draw_tiny_clock(f: &mut Frame, area: Rect, app: &App) {
+    let breath = app.get_breathing_scale(0.0);
+    let glyph = ["·", "∘", "○", "◯"][((breath * 3.0).round() as usize).min(3)];
+    let line = Line::from(vec![
+        Span::styled(format!("{} ", glyph), Style::default().fg(GOLD)),
+        Span::styled(
+            app.virtual_time.format("%H:%M:%S").to_string(),
+            Style::default().fg(PALE).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("  CHRONOS AETERNUM", Style::default().fg(GOLD_DIM)),
+    ]);
+    f.render_widget(Paragraph::new(line).alignment(Alignment::Center), area);
+}
+
+/// The inner watch followed by every ring, newest first, with the cursor's ring highlighted.
+pub fn draw_ring_list(f: &mut Frame, area: Rect, app: &App) {
+    let mut items = vec![ListItem::new(Line::from(vec![
+        Span::styled("◎ ", Style::default().fg(GOLD)),
+        Span::raw(format!("Today  {}", app.virtual_time.format("%a"))),
+    ]))];
+    for ring in &app.rings {
+        // Drought days fade into the list as they do on the canvas
+        let (glyph, count) = if ring.times.is_empty() { ("· ", Color::DarkGray) } else { ("○ ", AMBER) };
+        items.push(ListItem::new(Line::from(vec![
+            Span::styled(glyph, Style::default().fg(GOLD_DIM)),
+            Span::raw(format!("{}  ", ring.label())),
+            Span::styled(format!("{}", ring.notes()), Style::default().fg(count)),
+        ])));
+    }
+
+    let mut state = ListState::default().with_selected(Some(app.selected_ring_index.min(app.rings.len())));
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(" RINGS ").style(Style::default().fg(GOLD)))
+        .style(Style::default().fg(PALE))
+        .highlight_style(Style::default().fg(Color::Black).bg(GOLD).add_modifier(Modifier::BOLD));
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+/// The sixty minutes of the current hour on the cursor's ring, one cell each.
+pub fn draw_minute_strip(f: &mut Frame, area: Rect, app: &App) {
+    let date = app.selected_date();
+    let hour = app.selected_hour();
+
+    let gathered = app.selected_ring().filter(|ring| ring.period != Period::Day);
+    let cells: Vec<Span> = (0..60)
+        .map(|m| {
+            let note = app.notes.get(&crate::note_key(date, hour, m));
+            let (glyph, color) = match note {
+                _ if gathered.is_some_and(|ring| ring.times.contains_key(&(hour, m))) => {
+                    let count = gathered.and_then(|ring| ring.times.get(&(hour, m))).copied().unwrap_or(0);
+                    let peak = gathered.and_then(|ring| ring.times.values().max()).copied().unwrap_or(1);
+                    (if rings::density(count, peak) > 0.6 { "●" } else { "•" }, AMBER)
+                }
+                _ if gathered.is_some() => (if m % 5 == 0 { "┆" } else { "·" }, GOLD_DIM),
+                Some(n) if n.is_encrypted() => ("◆", GARNET),
+                Some(n) if n.is_locked => ("●", GOLD),
+                Some(_) => ("●", AMBER),
+                None if m % 5 == 0 => ("┆", GOLD_DIM),
+                None => ("·", GOLD_DIM),
+            };
+            let style = if app.selected_minute == Some(m) {
+                Style::default().fg(Color::Black).bg(PALE)
+            } else {
+                Style::default().fg(color)
+            };
+            Span::styled(glyph, style)
+        })
+        .collect();
+
+    // Keep the cursor in view when the strip is wider than the terminal
+    let inner = area.width.saturating_sub(2);
+    let offset = app.selected_minute.map_or(0, |m| (m as u16 + 1).saturating_sub(inner)).min(60);
+
+    let title = match app.selected_ring() {
+        Some(ring) if ring.period != Period::Day => format!(" {} {:02}:-- ", ring.label(), hour),
+        _ => format!(" {} {:02}:-- ", date, hour),
+    };
+    f.render_widget(
+        Paragraph::new(Line::from(cells))
+            .scroll((0, offset))
+            .block(Block::default().borders(Borders::ALL).title(title).style(Style::default().fg(GOLD))),
+        area,
+    );
+}
+
+/// What is banked under the cursor: key, seal, tags, revisions and the first lines.
+pub fn draw_node_detail(f: &mut Frame, area: Rect, app: &App) {
+    let mut lines = Vec::new();
+    match app.selected_minute {
+        None => lines.push(Line::from(Span::styled("Choose a minute with ← →", Style::default().fg(Color::DarkGray)))),
+        Some(m) if app.selected_key().is_none() => {
+            // A week, month or year ring: how many notes it gathers at this minute
+            if let Some(ring) = app.selected_ring() {
+                let hour = app.selected_hour();
+                let count = ring.times.get(&(hour, m)).copied().unwrap_or(0);
+                lines.push(Line::from(Span::styled(ring.label(), Style::default().fg(GOLD).add_modifier(Modifier::BOLD))));
+                lines.push(Line::from(format!("{} note(s) at {:02}:{:02}", count, hour, m)));
+                lines.push(Line::from(Span::styled(
+                    format!("{} note(s) in the whole ring", ring.notes()),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+        }
+        Some(m) => {
+            let key = app.get_date_key(m);
+            let when = parse_note_key(&key).map(|(d, h, m)| format!("{} {:02}:{:02}", d, h, m)).unwrap_or(key.clone());
+            lines.push(Line::from(Span::styled(when, Style::default().fg(GOLD).add_modifier(Modifier::BOLD))));
+
+            match app.notes.get(&key) {
+                None => lines.push(Line::from(Span::styled("An empty minute. Enter to write.", Style::default().fg(Color::DarkGray)))),
+                Some(note) if note.is_encrypted() => {
+                    lines.push(Line::from(Span::styled("Sealed under its own passphrase.", Style::default().fg(GARNET))));
+                }
+                Some(note) => {
+                    if note.is_locked {
+                        lines.push(Line::from(Span::styled("Sealed with a gilded band.", Style::default().fg(GOLD))));
+                    }
+                    let tags = extract_tags(&note.content);
+                    if !tags.is_empty() {
+                        let tags: Vec<String> = tags.iter().map(|t| format!("#{}", t)).collect();
+                        lines.push(Line::from(Span::styled(tags.join(" "), Style::default().fg(AMBER))));
+                    }
+                    lines.push(Line::from(""));
+                    lines.extend(note.content.lines().map(|l| Line::from(l.to_string())));
+                }
+            }
+            if let Some(note) = app.notes.get(&key).filter(|n| !n.revisions.is_empty()) {
+                lines.push(Line::from(""));
+                lines.push(Line::from(Span::styled(
+                    format!("{} revision(s) set aside", note.revisions.len()),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+        }
+    }
+
+    f.render_widget(
+        Paragraph::new(lines)
+            .style(Style::default().fg(PALE))
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(" NODE ").style(Style::default().fg(GOLD))),
+        area,
+    );
+}
+
+/// The whole instrument as lists, for terminals too small for the astrolabe.
+pub fn draw_compact(f: &mut Frame, area: Rect, app: &App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(3), Constraint::Length(5)])
+        .split(area);
+
+    draw_tiny_clock(f, rows[0], app);
+    draw_ring_list(f, rows[1], app);
+    draw_minute_strip(f, rows[2], app);
+    draw_node_detail(f, rows[3], app);
+}
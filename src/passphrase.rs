@@ -0,0 +1,76 @@
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use zeroize::Zeroizing;
+
+// --- Passphrase Prompt ---
+// A masked single-line input used wherever the instrument needs a secret.
+
+pub enum PromptAction {
+    Pending,
+    Submit(Zeroizing<String>),
+    Cancel,
+}
+
+pub struct PassphrasePrompt {
+    title: String,
+    input: Zeroizing<String>,
+    pub error: Option<String>,
+    pub busy: bool,
+}
+
+impl PassphrasePrompt {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self { title: title.into(), input: Zeroizing::new(String::new()), error: None, busy: false }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PromptAction {
+        match key.code {
+            KeyCode::Esc => PromptAction::Cancel,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => PromptAction::Cancel,
+            KeyCode::Enter if !self.input.is_empty() => {
+                let secret = Zeroizing::new(std::mem::take(&mut *self.input));
+                PromptAction::Submit(secret)
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                PromptAction::Pending
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                self.error = None;
+                PromptAction::Pending
+            }
+            _ => PromptAction::Pending,
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let gold = Color::Rgb(212, 175, 55);
+        f.render_widget(Clear, area);
+
+        let mask: String = "•".repeat(self.input.chars().count());
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(format!(" {}▏", mask), Style::default().fg(Color::Rgb(252, 246, 186)))),
+            Line::from(""),
+        ];
+        if self.busy {
+            lines.push(Line::from(Span::styled(" Deriving key...", Style::default().fg(Color::DarkGray))));
+        } else if let Some(error) = &self.error {
+            lines.push(Line::from(Span::styled(format!(" {}", error), Style::default().fg(Color::Red))));
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .style(Style::default().fg(gold))
+            .title(Span::styled(format!(" {} ", self.title), Style::default().fg(gold).add_modifier(Modifier::BOLD)))
+            .title_bottom(Line::from(" [ENTER] UNSEAL | [ESC] RETURN ").alignment(Alignment::Right));
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
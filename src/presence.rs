@@ -0,0 +1,154 @@
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, collections::HashMap, time::Duration};
+
+use crate::{archivist::Job, App, Screen, TimeNote};
+
+// --- Experience Units ---
+// Presence measured rather than read off the clock: the time spent breathing
+// with the astrolabe or in a guided session, the time spent writing in a
+// vault and the nodes sealed.
+// The seconds are kept per day in a ledger beside the archive (sealed like
+// it), and every instance adds what it saw to the ledger on disk rather than
+// writing over it.
+
+/// Without a key pressed for this long, attention is taken to have wandered off.
+const ATTENTION_SPAN: Duration = Duration::from_secs(10 * 60);
+/// The astrolabe breathes on its own; outside a guided session it counts only
+/// this long after a key, so a canvas left open does not bank minutes.
+const GAZE_SPAN: Duration = Duration::from_secs(60);
+/// A tick longer than this (a suspended machine) counts only this much.
+const LONGEST_TICK: Duration = Duration::from_secs(1);
+/// Presence is handed to the archivist once this many seconds are unbanked.
+const BANK_AFTER_SECS: f64 = 60.0;
+/// Units a sealed node is worth; a minute of presence is worth one.
+const UNITS_PER_SEALED_NODE: u64 = 5;
+
+#[derive(Clone, Copy)]
+pub enum Presence {
+    Breathing,
+    Writing,
+}
+
+/// Seconds of presence per day.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    #[serde(default)]
+    pub breathing: BTreeMap<NaiveDate, f64>,
+    #[serde(default)]
+    pub writing: BTreeMap<NaiveDate, f64>,
+}
+
+impl Ledger {
+    pub fn add(&mut self, presence: Presence, day: NaiveDate, secs: f64) {
+        let days = match presence {
+            Presence::Breathing => &mut self.breathing,
+            Presence::Writing => &mut self.writing,
+        };
+        *days.entry(day).or_default() += secs;
+    }
+
+    pub fn absorb(&mut self, other: &Ledger) {
+        for (&day, &secs) in &other.breathing {
+            self.add(Presence::Breathing, day, secs);
+        }
+        for (&day, &secs) in &other.writing {
+            self.add(Presence::Writing, day, secs);
+        }
+    }
+
+    pub fn breathing_secs(&self) -> f64 {
+        self.breathing.values().sum()
+    }
+
+    pub fn writing_secs(&self) -> f64 {
+        self.writing.values().sum()
+    }
+
+    pub fn on(&self, presence: Presence, day: NaiveDate) -> f64 {
+        let days = match presence {
+            Presence::Breathing => &self.breathing,
+            Presence::Writing => &self.writing,
+        };
+        days.get(&day).copied().unwrap_or(0.0)
+    }
+
+    /// Average writing time over the days anything was written.
+    pub fn writing_per_day(&self) -> f64 {
+        let days = self.writing.values().filter(|&&secs| secs > 0.0).count();
+        if days == 0 {
+            0.0
+        } else {
+            self.writing_secs() / days as f64
+        }
+    }
+
+    /// One unit per whole minute of presence, and a few for each sealed node.
+    pub fn experience_units(&self, sealed_nodes: usize) -> u64 {
+        ((self.breathing_secs() + self.writing_secs()) / 60.0) as u64 + sealed_nodes as u64 * UNITS_PER_SEALED_NODE
+    }
+}
+
+pub fn sealed_nodes(notes: &HashMap<String, TimeNote>) -> usize {
+    notes.values().filter(|note| note.is_locked).count()
+}
+
+/// `95` -> `1m 35s`, `7500` -> `2h 05m`.
+pub fn format_secs(secs: f64) -> String {
+    let secs = secs as u64;
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+impl App<'_> {
+    /// Counts the real time of a tick toward today's presence: writing while a
+    /// vault is open, breathing in a guided session or while the astrolabe is
+    /// on screen and in use.
+    pub fn attend(&mut self, elapsed: Duration) {
+        // A guided session needs no keys to be attended to
+        let presence = if self.breath.is_running() {
+            Presence::Breathing
+        } else if self.last_input.elapsed() > ATTENTION_SPAN {
+            return;
+        } else if self.is_editing {
+            Presence::Writing
+        } else if self.screen == Screen::Astrolabe && !self.is_modal() && self.last_input.elapsed() <= GAZE_SPAN {
+            Presence::Breathing
+        } else {
+            return;
+        };
+        self.unbanked.add(presence, Local::now().date_naive(), elapsed.min(LONGEST_TICK).as_secs_f64());
+    }
+
+    /// Hands unbanked presence to the archivist; `now` skips waiting for a full minute.
+    pub fn bank_presence(&mut self, now: bool) {
+        let unbanked = self.unbanked.breathing_secs() + self.unbanked.writing_secs();
+        if self.ledger_busy || unbanked == 0.0 || (!now && unbanked < BANK_AFTER_SECS) {
+            return;
+        }
+        self.ledger_busy = true;
+        self.archivist.send(Job::Presence(std::mem::take(&mut self.unbanked)));
+    }
+
+    pub fn on_banked(&mut self, banked: Ledger, result: anyhow::Result<Ledger>) {
+        self.ledger_busy = false;
+        match result {
+            Ok(ledger) => self.presence = ledger,
+            Err(e) => {
+                // Kept for the next attempt
+                self.unbanked.absorb(&banked);
+                self.status = Some(format!("PRESENCE NOT BANKED: {:#}", e));
+            }
+        }
+    }
+
+    /// The ledger on disk with what this instance has not banked yet.
+    pub fn ledger(&self) -> Ledger {
+        let mut ledger = self.presence.clone();
+        ledger.absorb(&self.unbanked);
+        ledger
+    }
+}
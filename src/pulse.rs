@@ -0,0 +1,99 @@
+use ratatui::crossterm::event::{self, Event};
+use std::{thread, time::Duration};
+use tokio::{
+    sync::mpsc::UnboundedSender,
+    time::{self, MissedTickBehavior},
+};
+
+use crate::{archivist, crypto, rings::Ring};
+
+// --- The Pulse ---
+// Everything that can wake the instrument arrives as a pulse on one channel:
+// keys from the terminal, the frame tick, replies from the archivist and the
+// results of background chores. The main loop only ever waits on that channel.
+
+pub enum Pulse {
+    Input(Event),
+    /// Time to advance the animation and draw a frame.
+    Tick,
+    Disk(archivist::Reply),
+    /// Rings regrown from the notes of the given generation.
+    Indexed { generation: u64, rings: Vec<Ring> },
+    /// A passphrase prompt's key, stretched off the main loop.
+    KeyDerived(anyhow::Result<crypto::Key>),
+    /// Time to look for changes other writers made to the archive.
+    WatchDue,
+    AutosaveDue,
+    SyncDue(String),
+    /// The process was told to stop (SIGINT, SIGTERM or SIGHUP).
+    Signal,
+}
+
+/// Forwards terminal events from a dedicated thread, since reading them blocks.
+pub fn spawn_input(pulses: UnboundedSender<Pulse>) {
+    thread::spawn(move || {
+        while !pulses.is_closed() {
+            match event::poll(Duration::from_millis(100)) {
+                Ok(false) => {}
+                Ok(true) => match event::read() {
+                    Ok(event) => {
+                        if pulses.send(Pulse::Input(event)).is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                },
+                Err(_) => break,
+            }
+        }
+    });
+}
+
+/// Sends a pulse every `period`, skipping beats the loop was too busy to take.
+pub fn every(period: Duration, pulses: UnboundedSender<Pulse>, pulse: impl Fn() -> Pulse + Send + 'static) {
+    tokio::spawn(async move {
+        let mut interval = time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            if pulses.send(pulse()).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Turns SIGINT, SIGTERM and SIGHUP into a pulse, so unsaved notes are banked before leaving.
+#[cfg(unix)]
+pub fn spawn_signals(pulses: UnboundedSender<Pulse>) -> std::io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut hangup = signal(SignalKind::hangup())?;
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = interrupt.recv() => {}
+                _ = terminate.recv() => {}
+                _ = hangup.recv() => {}
+            }
+            if pulses.send(Pulse::Signal).is_err() {
+                break;
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn spawn_signals(pulses: UnboundedSender<Pulse>) -> std::io::Result<()> {
+    tokio::spawn(async move {
+        while tokio::signal::ctrl_c().await.is_ok() {
+            if pulses.send(Pulse::Signal).is_err() {
+                break;
+            }
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,224 @@
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use std::collections::HashMap;
+
+use crate::{
+    archivist::{Job, LoadPurpose, Reply},
+    merge,
+    store::SaveOutcome,
+    App, TimeNote,
+};
+
+// --- Reconciliation ---
+// Saving never silently replaces notes another instance wrote. When the
+// archive changed under us, the user chooses to merge or to reload. While
+// idle, changes on disk are picked up on their own. All disk work goes
+// through the archivist; its answers arrive here.
+
+impl App<'_> {
+    /// Hands the notes to the archivist. A save asked for while it is busy
+    /// follows as soon as its current job has been answered.
+    pub fn persist(&mut self) {
+        self.reindex();
+        if self.disk_busy {
+            self.save_pending = true;
+            return;
+        }
+        self.disk_busy = true;
+        self.archivist.send(Job::Save { notes: self.notes.clone(), settled: std::mem::take(&mut self.settled_drafts) });
+    }
+
+    pub fn on_disk(&mut self, reply: Reply) {
+        // Drafts are kept alongside; they never held the archive up.
+        if !matches!(reply, Reply::Drafted(_) | Reply::Banked { .. }) {
+            self.disk_busy = false;
+        }
+        match reply {
+            Reply::Saved { notes, outcome } => match outcome {
+                Ok(SaveOutcome::Written) => {
+                    self.baseline = notes;
+                    if std::mem::take(&mut self.save_failed) {
+                        self.status = None;
+                    }
+                }
+                Ok(SaveOutcome::ChangedOnDisk) => {
+                    self.external_change = true;
+                    self.save_pending = false;
+                    self.save_failed = true;
+                    self.status = Some("ARCHIVE CHANGED ON DISK: NOT SAVED".to_string());
+                }
+                Err(e) => {
+                    self.save_failed = true;
+                    self.status = Some(format!("ARCHIVE NOT SAVED: {:#}", e));
+                }
+            },
+            Reply::Loaded { purpose: LoadPurpose::Merge, result } => self.merge_external(result),
+            Reply::Loaded { purpose: LoadPurpose::Reload, result } => self.reload_external(result),
+            Reply::Polled(Ok(None)) => {}
+            Reply::Polled(Ok(Some(theirs))) => {
+                self.status = None;
+                self.absorb(theirs);
+            }
+            // Possibly caught mid-write by a tool that does not write atomically; retry next poll
+            Reply::Polled(Err(e)) => self.status = Some(format!("ARCHIVE NOT RELOADED: {:#}", e)),
+            Reply::Synced(Ok((report, theirs))) => {
+                if !report.conflicts.is_empty() {
+                    self.status = Some(format!("SYNCED; {} MINUTES DIFFERED, BOTH VERSIONS KEPT", report.conflicts.len()));
+                }
+                self.absorb(theirs);
+            }
+            Reply::Synced(Err(e)) => self.status = Some(format!("SYNC FAILED: {:#}", e)),
+            Reply::Drafted(Ok(())) => {}
+            Reply::Drafted(Err(e)) => self.status = Some(format!("DRAFT NOT KEPT: {:#}", e)),
+            Reply::Banked { banked, result } => self.on_banked(banked, result),
+        }
+
+        if self.save_pending && !self.external_change {
+            self.save_pending = false;
+            self.persist();
+        }
+    }
+
+    pub fn handle_external_change_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('m') | KeyCode::Char('M') => self.load_external(LoadPurpose::Merge),
+            KeyCode::Char('r') | KeyCode::Char('R') => self.load_external(LoadPurpose::Reload),
+            KeyCode::Esc => self.external_change = false,
+            _ => {}
+        }
+    }
+
+    fn load_external(&mut self, purpose: LoadPurpose) {
+        self.external_change = false;
+        self.disk_busy = true;
+        self.archivist.send(Job::Load(purpose));
+    }
+
+    /// Folds the other writer's notes into ours, using the last synced state as ancestor.
+    fn merge_external(&mut self, theirs: anyhow::Result<HashMap<String, TimeNote>>) {
+        let theirs = match theirs {
+            Ok(theirs) => theirs,
+            Err(e) => {
+                self.status = Some(format!("ARCHIVE NOT MERGED: {:#}", e));
+                return;
+            }
+        };
+
+        let merged = merge::merge(Some(&self.baseline), &self.notes, &theirs, "another instance");
+        self.notes = merged.notes;
+        self.baseline = theirs;
+        self.status = (!merged.conflicts.is_empty())
+            .then(|| format!("MERGED; {} MINUTES DIFFERED, BOTH VERSIONS KEPT", merged.conflicts.len()));
+        self.save_failed = false;
+        self.persist();
+    }
+
+    /// WatchDue: asks the archivist whether other writers changed the archive.
+    /// Not while a save is queued, so that no save is based on a stale read.
+    pub fn poll_archive(&mut self) {
+        if self.disk_busy || self.save_pending || self.external_change || self.prompt.is_some() {
+            return;
+        }
+        self.disk_busy = true;
+        self.archivist.send(Job::Poll);
+    }
+
+    /// SyncDue: exchanges changes with the configured remote while the archive is idle.
+    pub fn sync_archive(&mut self, url: String) {
+        if self.disk_busy || self.save_pending || self.external_change || self.notes != self.baseline {
+            return;
+        }
+        self.disk_busy = true;
+        self.archivist.send(Job::Sync(url));
+    }
+
+    /// AutosaveDue: keeps a draft of the open vault and retries a save that
+    /// failed, unless the user has yet to choose between merging and reloading.
+    pub fn autosave(&mut self) {
+        self.keep_draft();
+        if !self.external_change && !self.disk_busy && self.notes != self.baseline {
+            self.persist();
+        }
+    }
+
+    /// Banks an open vault and anything else unsaved ahead of an unplanned exit.
+    /// Notes that could not be saved over another writer's changes become drafts.
+    pub fn rescue(&mut self) {
+        if self.is_editing {
+            self.close_vault();
+        }
+        if self.external_change {
+            for (key, note) in &self.notes {
+                if self.baseline.get(key) != Some(note) {
+                    self.archivist.send(Job::Draft { key: key.clone(), draft: Some(note.clone()) });
+                }
+            }
+        } else if self.notes != self.baseline && !self.disk_busy {
+            self.persist();
+        }
+    }
+
+    /// Takes in archive changes made elsewhere, keeping the cursor on its ring
+    /// and leaving an open vault untouched (it is banked on Esc as usual).
+    fn absorb(&mut self, theirs: HashMap<String, TimeNote>) {
+        if self.notes == self.baseline {
+            self.notes = theirs.clone();
+        } else {
+            self.notes = merge::merge(Some(&self.baseline), &self.notes, &theirs, "another instance").notes;
+        }
+        self.baseline = theirs;
+        self.reindex();
+    }
+
+    /// Drops unsaved notes in favour of what is on disk.
+    fn reload_external(&mut self, result: anyhow::Result<HashMap<String, TimeNote>>) {
+        match result {
+            Ok(notes) => {
+                self.baseline = notes.clone();
+                self.notes = notes;
+                self.status = None;
+                self.save_failed = false;
+                self.reindex();
+            }
+            Err(e) => self.status = Some(format!("ARCHIVE NOT RELOADED: {:#}", e)),
+        }
+    }
+}
+
+pub fn draw_external_change(f: &mut Frame, area: Rect) {
+    let gold = Color::Rgb(212, 175, 55);
+    f.render_widget(Clear, area);
+
+    let text = vec![
+        Line::from(""),
+        Line::from(" The archive was changed by another instance while this one held unsaved notes."),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(" [M]", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::raw(" Merge both   "),
+            Span::styled("[R]", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::raw(" Reload, dropping unsaved notes   "),
+            Span::styled("[ESC]", Style::default().fg(gold).add_modifier(Modifier::BOLD)),
+            Span::raw(" Later"),
+        ]),
+    ];
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().fg(gold))
+        .title(Span::styled(" TEMPORAL DIVERGENCE ", Style::default().fg(gold).add_modifier(Modifier::BOLD)))
+        .title_alignment(Alignment::Left);
+    f.render_widget(
+        Paragraph::new(text)
+            .style(Style::default().fg(Color::Rgb(252, 246, 186)))
+            .wrap(Wrap { trim: false })
+            .block(block),
+        area,
+    );
+}
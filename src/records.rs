@@ -0,0 +1,83 @@
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+use std::{collections::HashMap, io::Write};
+
+use crate::{extract_tags, parse_note_key, TimeNote};
+
+// --- Tabular Export ---
+// One row per note for analysis pipelines. Rows are written as they are
+// produced, so the output never has to be assembled in memory.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Csv,
+    Jsonl,
+}
+
+const CSV_HEADER: &str = "date,hour,minute,weekday,content_length,tags,locked";
+
+#[derive(Serialize)]
+struct Record {
+    date: String,
+    hour: u32,
+    minute: u32,
+    weekday: String,
+    content_length: usize,
+    tags: Vec<String>,
+    locked: bool,
+}
+
+impl Record {
+    fn new(date: NaiveDate, hour: u32, minute: u32, note: &TimeNote) -> Self {
+        Self {
+            date: date.format("%Y-%m-%d").to_string(),
+            hour,
+            minute,
+            weekday: date.weekday().to_string(),
+            content_length: note.content.chars().count(),
+            tags: extract_tags(&note.content),
+            locked: note.is_locked,
+        }
+    }
+}
+
+pub fn export(notes: &HashMap<String, TimeNote>, format: Format, out: &mut impl Write) -> std::io::Result<()> {
+    let mut keys: Vec<&String> = notes.keys().collect();
+    keys.sort();
+
+    if format == Format::Csv {
+        writeln!(out, "{}", CSV_HEADER)?;
+    }
+
+    for key in keys {
+        let Some((date, hour, minute)) = parse_note_key(key) else { continue };
+        let record = Record::new(date, hour, minute, &notes[key]);
+        match format {
+            Format::Csv => writeln!(
+                out,
+                "{},{},{},{},{},{},{}",
+                record.date,
+                record.hour,
+                record.minute,
+                record.weekday,
+                record.content_length,
+                csv_field(&record.tags.join(";")),
+                record.locked
+            )?,
+            Format::Jsonl => {
+                serde_json::to_writer(&mut *out, &record)?;
+                out.write_all(b"\n")?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,186 @@
+use chrono::{Datelike, NaiveDate, Timelike};
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+use std::collections::HashMap;
+
+use crate::{
+    panels::{AMBER, GOLD, PALE},
+    parse_note_key, App, TimeNote,
+};
+
+// --- Bioluminescent Resonance ---
+// The canvas lights a thread to every node at the present minute; the
+// resonance panel reads them out. It lists the notes written at this same
+// minute on other days or, turned to "on this day", the notes written on
+// this date in earlier months and years. The list follows the present as
+// it moves, and any note in it can be opened.
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Reach {
+    /// The same hour and minute, on any other day.
+    Minute,
+    /// The same day of the month, in earlier months and years.
+    OnThisDay,
+}
+
+pub struct Resonance {
+    pub reach: Reach,
+    pub selected: usize,
+}
+
+pub struct Echo {
+    pub key: String,
+    pub date: NaiveDate,
+    pub hour: u32,
+    pub minute: u32,
+}
+
+/// Notes resonating with `today` at `hour:minute`, newest first.
+pub fn echoes(notes: &HashMap<String, TimeNote>, today: NaiveDate, hour: u32, minute: u32, reach: Reach) -> Vec<Echo> {
+    let mut echoes: Vec<Echo> = notes
+        .keys()
+        .filter_map(|key| {
+            let (date, h, m) = parse_note_key(key)?;
+            let resonates = match reach {
+                Reach::Minute => date != today && (h, m) == (hour, minute),
+                Reach::OnThisDay => date < today && date.day() == today.day(),
+            };
+            resonates.then(|| Echo { key: key.clone(), date, hour: h, minute: m })
+        })
+        .collect();
+    echoes.sort_by_key(|echo| std::cmp::Reverse((echo.date, echo.hour, echo.minute)));
+    echoes
+}
+
+/// How long ago `date` was, in the terms of the reach it was found by.
+fn ago(date: NaiveDate, today: NaiveDate, reach: Reach) -> String {
+    let plural = |n: i64, unit: &str| format!("{} {}{} ago", n, unit, if n == 1 { "" } else { "s" });
+    match reach {
+        Reach::Minute if date > today => format!("in {} day(s)", (date - today).num_days()),
+        Reach::Minute => plural((today - date).num_days(), "day"),
+        Reach::OnThisDay => {
+            let months = (today.year() - date.year()) as i64 * 12 + today.month() as i64 - date.month() as i64;
+            if months % 12 == 0 {
+                plural(months / 12, "year")
+            } else {
+                plural(months, "month")
+            }
+        }
+    }
+}
+
+impl App<'_> {
+    pub fn open_resonance(&mut self) {
+        self.resonance = Some(Resonance { reach: Reach::Minute, selected: 0 });
+    }
+
+    /// The notes the open panel lists, at the present virtual minute.
+    fn current_echoes(&self) -> Vec<Echo> {
+        let Some(resonance) = &self.resonance else { return Vec::new() };
+        let now = self.virtual_time;
+        echoes(&self.notes, now.date_naive(), now.hour(), now.minute(), resonance.reach)
+    }
+
+    pub fn handle_resonance_key(&mut self, key: KeyEvent) {
+        let count = self.current_echoes().len();
+        let Some(resonance) = self.resonance.as_mut() else { return };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('r') => self.resonance = None,
+            KeyCode::Tab => {
+                resonance.reach = if resonance.reach == Reach::Minute { Reach::OnThisDay } else { Reach::Minute };
+                resonance.selected = 0;
+            }
+            KeyCode::Up => resonance.selected = resonance.selected.saturating_sub(1),
+            KeyCode::Down => resonance.selected = (resonance.selected + 1).min(count.saturating_sub(1)),
+            KeyCode::Enter => self.open_echo(),
+            _ => {}
+        }
+    }
+
+    /// Opens the selected note, leaving the cursor on its node when the layout shows it.
+    fn open_echo(&mut self) {
+        let selected = self.resonance.as_ref().map_or(0, |r| r.selected);
+        let mut echoes = self.current_echoes();
+        if echoes.is_empty() {
+            return;
+        }
+        // The present may have moved on, leaving a shorter list than the one chosen from
+        let echo = echoes.swap_remove(selected.min(echoes.len() - 1));
+        self.resonance = None;
+        self.select_node(echo.date, echo.hour, echo.minute);
+        self.open_node(echo.key);
+    }
+}
+
+pub fn draw_resonance(f: &mut Frame, area: Rect, app: &App) {
+    let Some(resonance) = &app.resonance else { return };
+    let today = app.virtual_time.date_naive();
+    let echoes = app.current_echoes();
+    f.render_widget(Clear, area);
+
+    let title = match resonance.reach {
+        Reach::Minute => format!(" RESONANCE AT {} ", app.virtual_time.format("%H:%M")),
+        Reach::OnThisDay => format!(" ON THIS DAY, THE {}{} ", today.day(), ordinal(today.day())),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(Span::styled(title, Style::default().fg(GOLD).add_modifier(Modifier::BOLD)))
+        .title_bottom(Line::from(" ↑↓ (Choose) | Enter (Open) | Tab (Minute / On this day) | r / Esc (Close) ").right_aligned())
+        .style(Style::default().fg(GOLD));
+
+    if echoes.is_empty() {
+        let silence = match resonance.reach {
+            Reach::Minute => "No other day was written in at this minute.",
+            Reach::OnThisDay => "Nothing was written on this date in earlier months.",
+        };
+        let item = ListItem::new(Line::from(Span::styled(format!(" {}", silence), Style::default().fg(Color::DarkGray))));
+        f.render_widget(List::new(vec![item]).block(block), area);
+        return;
+    }
+
+    let width = area.width.saturating_sub(6) as usize;
+    let items: Vec<ListItem> = echoes
+        .iter()
+        .map(|echo| {
+            let note = &app.notes[&echo.key];
+            let preview = if note.is_encrypted() {
+                "(sealed under its own passphrase)".to_string()
+            } else {
+                note.content.lines().find(|l| !l.trim().is_empty()).unwrap_or_default().chars().take(width).collect()
+            };
+            ListItem::new(vec![
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} {:02}:{:02}", echo.date.format("%Y-%m-%d %a"), echo.hour, echo.minute),
+                        Style::default().fg(AMBER),
+                    ),
+                    Span::styled(format!("  {}", ago(echo.date, today, resonance.reach)), Style::default().fg(Color::DarkGray)),
+                ]),
+                Line::from(Span::styled(format!("  {}", preview), Style::default().fg(PALE))),
+            ])
+        })
+        .collect();
+
+    let mut state = ListState::default().with_selected(Some(resonance.selected.min(echoes.len() - 1)));
+    let list = List::new(items)
+        .block(block)
+        .highlight_symbol("▸ ")
+        .highlight_style(Style::default().bg(Color::Rgb(40, 34, 16)));
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+fn ordinal(day: u32) -> &'static str {
+    match (day % 10, day % 100) {
+        (_, 11..=13) => "TH",
+        (1, _) => "ST",
+        (2, _) => "ND",
+        (3, _) => "RD",
+        _ => "TH",
+    }
+}
@@ -0,0 +1,249 @@
+use chrono::{Datelike, Months, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{parse_note_key, TimeNote};
+
+// --- Dendrochronology Geometry ---
+// Shared by the terminal canvas and the static exporters so that a ring
+// drawn anywhere sits at the same radius and angle.
+
+/// Half-extent of the canvas coordinate space on both axes.
+pub const CANVAS_BOUND: f64 = 250.0;
+/// Radius of the minute ticks of the inner watch.
+pub const INNER_WATCH_RADIUS: f64 = 100.0;
+/// Where the apexes of the lotus petals reach.
+pub const PETAL_RADIUS: f64 = 118.0;
+pub const RING_BASE_RADIUS: f64 = 140.0;
+pub const RING_SPACING: f64 = 30.0;
+/// Rings inside the knee of the compressed layout keep nearly full spacing.
+const COMPRESSION_KNEE: f64 = 7.0;
+
+/// How much time one ring gathers.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Period {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl Period {
+    /// The first day of the period that holds `date`; weeks start on Monday.
+    pub fn start_of(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Day => date,
+            Period::Week => date - chrono::Duration::days(date.weekday().num_days_from_monday() as i64),
+            Period::Month => date.with_day(1).unwrap_or(date),
+            Period::Year => date.with_ordinal(1).unwrap_or(date),
+        }
+    }
+
+    /// The last day of the period starting at `start`.
+    fn end_of(self, start: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Day => start,
+            Period::Week => start + chrono::Duration::days(6),
+            Period::Month => start + Months::new(1) - chrono::Duration::days(1),
+            Period::Year => start + Months::new(12) - chrono::Duration::days(1),
+        }
+    }
+}
+
+/// A ring of heartwood: a calendar date (or a week, month or year of them)
+/// and the times banked on it.
+pub struct Ring {
+    /// The first day the ring stands for.
+    pub date: NaiveDate,
+    pub period: Period,
+    /// Minutes of the day that hold notes, with how many notes fell on each.
+    pub times: BTreeMap<(u32, u32), u32>,
+    pub radius: f64,
+}
+
+impl Ring {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        date >= self.date && date <= self.period.end_of(self.date)
+    }
+
+    pub fn notes(&self) -> u32 {
+        self.times.values().sum()
+    }
+
+    pub fn label(&self) -> String {
+        match self.period {
+            Period::Day => self.date.format("%Y-%m-%d %a").to_string(),
+            Period::Week => self.date.format("Week %G-W%V").to_string(),
+            Period::Month => self.date.format("%B %Y").to_string(),
+            Period::Year => self.date.format("Year %Y").to_string(),
+        }
+    }
+}
+
+/// How dates are laid out as rings.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RingLayout {
+    /// One ring per date, evenly spaced.
+    Daily,
+    /// Only the dates of the last few days.
+    Window,
+    /// One ring per date, older rings pressed ever closer together.
+    Compressed,
+    /// The last week in days, then weeks, months and years, as a tree grows.
+    Seasons,
+    /// One ring per week (month, year), its nodes swelling with the notes they gather.
+    Weeks,
+    Months,
+    Years,
+}
+
+const LAYOUTS: [RingLayout; 7] = [
+    RingLayout::Daily,
+    RingLayout::Window,
+    RingLayout::Compressed,
+    RingLayout::Seasons,
+    RingLayout::Weeks,
+    RingLayout::Months,
+    RingLayout::Years,
+];
+
+impl RingLayout {
+    pub fn next(self) -> Self {
+        let i = LAYOUTS.iter().position(|&l| l == self).unwrap_or(0);
+        LAYOUTS[(i + 1) % LAYOUTS.len()]
+    }
+
+    pub fn prev(self) -> Self {
+        let i = LAYOUTS.iter().position(|&l| l == self).unwrap_or(0);
+        LAYOUTS[(i + LAYOUTS.len() - 1) % LAYOUTS.len()]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RingLayout::Daily => "DAILY",
+            RingLayout::Window => "WINDOW",
+            RingLayout::Compressed => "COMPRESSED",
+            RingLayout::Seasons => "SEASONS",
+            RingLayout::Weeks => "WEEKS",
+            RingLayout::Months => "MONTHS",
+            RingLayout::Years => "YEARS",
+        }
+    }
+
+    /// The period of the ring a date `age` days old falls in; `None` leaves it out.
+    fn period_for(self, age: i64, window_days: u32) -> Option<Period> {
+        match self {
+            RingLayout::Daily | RingLayout::Compressed => Some(Period::Day),
+            RingLayout::Window => (age < window_days as i64).then_some(Period::Day),
+            RingLayout::Seasons => Some(match age {
+                ..7 => Period::Day,
+                7..35 => Period::Week,
+                35..365 => Period::Month,
+                _ => Period::Year,
+            }),
+            RingLayout::Weeks => Some(Period::Week),
+            RingLayout::Months => Some(Period::Month),
+            RingLayout::Years => Some(Period::Year),
+        }
+    }
+
+    fn radius(self, index: usize) -> f64 {
+        match self {
+            // Logarithmic: full spacing near the heart, a fraction of it a year out
+            RingLayout::Compressed => {
+                RING_BASE_RADIUS + RING_SPACING * COMPRESSION_KNEE * (1.0 + index as f64 / COMPRESSION_KNEE).ln()
+            }
+            _ => ring_radius(index),
+        }
+    }
+}
+
+/// The ring layout chosen at runtime, passed along to wherever rings are grown.
+#[derive(Clone, Copy, Debug)]
+pub struct Growth {
+    pub layout: RingLayout,
+    /// How many days the rolling window reaches back.
+    pub window_days: u32,
+    /// Grows a ring for every calendar day since the first note, droughts included.
+    pub every_day: bool,
+}
+
+impl Growth {
+    pub fn describe(&self) -> String {
+        let layout = match self.layout {
+            RingLayout::Window => format!("LAST {} DAYS", self.window_days),
+            layout => layout.name().to_string(),
+        };
+        if self.every_day {
+            format!("{} + DROUGHTS", layout)
+        } else {
+            layout
+        }
+    }
+}
+
+impl Default for Growth {
+    fn default() -> Self {
+        Self { layout: RingLayout::Daily, window_days: 30, every_day: false }
+    }
+}
+
+/// Groups notes into rings, newest first (inner rings are the newest).
+/// `today` is where the ages of the window and the seasons are counted from.
+pub fn grow_rings(notes: &HashMap<String, TimeNote>, growth: &Growth, today: NaiveDate) -> Vec<Ring> {
+    let mut grouped: BTreeMap<NaiveDate, BTreeMap<(u32, u32), u32>> = BTreeMap::new();
+    for key in notes.keys() {
+        if let Some((date, h, m)) = parse_note_key(key) {
+            *grouped.entry(date).or_default().entry((h, m)).or_default() += 1;
+        }
+    }
+    if growth.every_day {
+        // The days nothing was written grow rings too, as droughts do in wood
+        if let Some(&first) = grouped.keys().next() {
+            for date in first.iter_days().take_while(|date| *date <= today) {
+                grouped.entry(date).or_default();
+            }
+        }
+    }
+
+    // Walking from the newest date, each date joins the previous ring when it falls in the same period
+    let mut rings: Vec<Ring> = Vec::new();
+    for (date, times) in grouped.into_iter().rev() {
+        let Some(period) = growth.layout.period_for((today - date).num_days(), growth.window_days) else { continue };
+        let start = period.start_of(date);
+        match rings.last_mut() {
+            Some(ring) if ring.period == period && ring.date == start => {
+                for (time, count) in times {
+                    *ring.times.entry(time).or_default() += count;
+                }
+            }
+            _ => rings.push(Ring { date: start, period, times, radius: 0.0 }),
+        }
+    }
+
+    for (i, ring) in rings.iter_mut().enumerate() {
+        ring.radius = growth.layout.radius(i);
+    }
+    rings
+}
+
+/// How full a minute of a gathered ring is next to the fullest one, from
+/// 0 to 1. The square root keeps a single note from vanishing beside a habit.
+pub fn density(count: u32, peak: u32) -> f64 {
+    (count as f64 / peak.max(1) as f64).sqrt().min(1.0)
+}
+
+pub fn ring_radius(index: usize) -> f64 {
+    RING_BASE_RADIUS + index as f64 * RING_SPACING
+}
+
+/// Angle of a node on its ring, in degrees counter-clockwise from east.
+/// Minute precision mapping: 1440 minutes in a day -> 360 degrees, midnight at the top.
+pub fn node_angle_deg(hour: u32, minute: u32) -> f64 {
+    let total_minutes = (hour * 60 + minute) as f64;
+    90.0 - (total_minutes / 1440.0) * 360.0
+}
+
+/// Angle of a minute tick on the inner watch, in degrees counter-clockwise from east.
+pub fn tick_angle_deg(minute: f64) -> f64 {
+    90.0 - minute * 6.0
+}
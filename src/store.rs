@@ -0,0 +1,478 @@
+use anyhow::{bail, Context, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use zeroize::Zeroize;
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::{
+    crypto::{KdfParams, Key, SealedBox},
+    TimeNote,
+};
+
+// --- The Eternal Script ---
+// The archive on disk is a versioned envelope around the note map. Older
+// archives are migrated forward on load (after a backup of the original
+// file); archives from a newer build are refused rather than half-read.
+// When a passphrase is set, the whole envelope is written as a sealed box.
+//
+// Several instances (or a sync tool) may share one archive. Every read and
+// write happens under an advisory lock on a sibling `.lock` file, and a save
+// first checks that the file is still the one this store last read or wrote.
+
+pub const SCHEMA_VERSION: u64 = 3;
+
+/// Each step lifts an archive from version `i` to `i + 1`.
+const MIGRATIONS: [fn(Value) -> Result<Value>; SCHEMA_VERSION as usize] = [
+    migrate_v0_bare_map,
+    migrate_v1_sealed_nodes,
+    migrate_v2_revisions,
+];
+
+#[derive(Serialize)]
+struct EnvelopeRef<'a> {
+    version: u64,
+    notes: &'a HashMap<String, TimeNote>,
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    notes: HashMap<String, TimeNote>,
+}
+
+/// What a checked save did.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SaveOutcome {
+    Written,
+    /// Someone else wrote the archive since we last saw it; nothing was written.
+    ChangedOnDisk,
+}
+
+/// Identity of the archive bytes on disk: modification time, length and content hash.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Fingerprint {
+    modified: Option<SystemTime>,
+    len: u64,
+    hash: u64,
+}
+
+pub struct Store {
+    path: PathBuf,
+    key: Option<Key>,
+    /// The archive as this store last read or wrote it; `None` when it did not exist.
+    seen: Option<Fingerprint>,
+}
+
+impl Store {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), key: None, seen: None }
+    }
+
+    /// Another file sealed (or not) exactly like this archive.
+    pub fn sibling(&self, path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into(), key: self.key.clone(), seen: None }
+    }
+
+    /// The snapshot kept as common ancestor for merging with `other`.
+    pub fn ancestor(&self, other: &str) -> Self {
+        let stem = self.stem();
+        let other = other.replace(|c: char| !c.is_alphanumeric() && c != '-' && c != '.', "_");
+        self.sibling(self.path.with_file_name(format!("{}.base-{}.json", stem, other)))
+    }
+
+    /// Where vault contents are kept while they are still being written.
+    pub fn drafts(&self) -> Self {
+        let stem = self.stem();
+        self.sibling(self.path.with_file_name(format!("{}.drafts.json", stem)))
+    }
+
+    /// Where the presence ledger is kept beside the archive.
+    pub fn presence(&self) -> Self {
+        let stem = self.stem();
+        self.sibling(self.path.with_file_name(format!("{}.presence.json", stem)))
+    }
+
+    /// Every snapshot kept as a common ancestor, whichever archive it is for.
+    pub fn ancestors(&self) -> Result<Vec<Store>> {
+        let prefix = format!("{}.base-", self.stem());
+        self.siblings_where(|name| name.starts_with(&prefix) && name.ends_with(".json"))
+    }
+
+    /// The migration backups kept beside the archive.
+    pub fn backups(&self) -> Result<Vec<Store>> {
+        let prefix = format!("{}.v", self.stem());
+        self.siblings_where(|name| {
+            // `<stem>.v<N>.bak.json` or `<stem>.v<N>.bak<n>.json`
+            let Some((version, rest)) = name.strip_prefix(&prefix).and_then(|rest| rest.split_once(".bak")) else {
+                return false;
+            };
+            let is_number = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+            !version.is_empty() && is_number(version) && rest.strip_suffix(".json").is_some_and(is_number)
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn stem(&self) -> &str {
+        self.path.file_stem().and_then(|s| s.to_str()).unwrap_or("chronos_notes")
+    }
+
+    /// Files in the archive's directory whose names match, sealed (or not) like the archive.
+    fn siblings_where(&self, matches: impl Fn(&str) -> bool) -> Result<Vec<Store>> {
+        let dir = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        let mut siblings = Vec::new();
+        for entry in fs::read_dir(dir).with_context(|| format!("cannot list {}", dir.display()))? {
+            let entry = entry?;
+            if entry.file_name().to_str().is_some_and(&matches) {
+                siblings.push(self.sibling(self.path.with_file_name(entry.file_name())));
+            }
+        }
+        siblings.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(siblings)
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// True when the archive on disk is sealed and cannot be loaded until unlocked.
+    pub fn is_sealed(&self) -> Result<bool> {
+        Ok(self.key.is_none() && matches!(self.read_raw()?, Some(raw) if is_sealed_box(&raw)))
+    }
+
+    /// Derives the archive key from `passphrase` and proves it against the file.
+    pub fn unlock(&mut self, passphrase: &str) -> Result<()> {
+        let Some(raw) = self.read_raw()? else { bail!("there is no archive to unlock") };
+        let sealed: SealedBox = serde_json::from_value(raw).context("the sealed archive is malformed")?;
+        let key = Key::derive(passphrase, &sealed.kdf)?;
+        key.open(&sealed)?;
+        self.key = Some(key);
+        Ok(())
+    }
+
+    /// Seals future saves under a new passphrase, or writes plaintext again with `None`.
+    pub fn set_passphrase(&mut self, passphrase: Option<&str>) -> Result<()> {
+        self.key = match passphrase {
+            Some(passphrase) => Some(Key::derive(passphrase, &KdfParams::generate())?),
+            None => None,
+        };
+        Ok(())
+    }
+
+    pub fn load(&mut self) -> Result<HashMap<String, TimeNote>> {
+        let _lock = self.lock()?;
+        self.read_notes()
+    }
+
+    /// Changes the notes in one locked read and write, for files other
+    /// instances change too (the drafts). A map left empty removes the file.
+    pub fn update_notes(&mut self, change: impl FnOnce(&mut HashMap<String, TimeNote>)) -> Result<()> {
+        let _lock = self.lock()?;
+        let mut notes = self.read_notes()?;
+        change(&mut notes);
+        if !notes.is_empty() {
+            return self.write(&notes);
+        }
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("cannot remove {}", self.path.display()))
+            }
+            _ => {
+                self.seen = None;
+                Ok(())
+            }
+        }
+    }
+
+    /// Reads (and migrates) the notes; callers hold the lock.
+    fn read_notes(&mut self) -> Result<HashMap<String, TimeNote>> {
+        let seen = self.fingerprint()?;
+        let Some(mut archive) = self.read_raw()? else {
+            self.seen = seen;
+            return Ok(HashMap::new());
+        };
+
+        if is_sealed_box(&archive) {
+            archive = self.open(archive)?;
+        }
+
+        let version = schema_version(&archive);
+        if version > SCHEMA_VERSION {
+            bail!(
+                "{} was written with archive schema v{}, but this build only understands up to v{}. \
+                 Refusing to open it so that no notes are lost; please upgrade Chronos Aeternum.",
+                self.path.display(),
+                version,
+                SCHEMA_VERSION
+            );
+        }
+
+        if version < SCHEMA_VERSION {
+            let backup = self.backup_path(version);
+            fs::copy(&self.path, &backup)
+                .with_context(|| format!("cannot back up {} before migrating", self.path.display()))?;
+
+            for migrate in &MIGRATIONS[version as usize..] {
+                archive = migrate(archive)?;
+            }
+        }
+
+        let envelope: Envelope = serde_json::from_value(archive)
+            .with_context(|| format!("{} does not match archive schema v{}", self.path.display(), SCHEMA_VERSION))?;
+
+        // Only an archive that was fully understood counts as seen
+        self.seen = seen;
+        if version < SCHEMA_VERSION {
+            self.write(&envelope.notes)?;
+        }
+        Ok(envelope.notes)
+    }
+
+    /// Writes the archive unless it changed on disk since this store last saw it.
+    pub fn save(&mut self, notes: &HashMap<String, TimeNote>) -> Result<SaveOutcome> {
+        let _lock = self.lock()?;
+        if self.fingerprint()? != self.seen {
+            return Ok(SaveOutcome::ChangedOnDisk);
+        }
+        self.write(notes)?;
+        Ok(SaveOutcome::Written)
+    }
+
+    /// Writes the file regardless of what is on disk; for snapshots this
+    /// process alone maintains.
+    pub fn replace(&mut self, notes: &HashMap<String, TimeNote>) -> Result<()> {
+        let _lock = self.lock()?;
+        self.write(notes)
+    }
+
+    /// Reads a plain value (not a note archive) kept in this file; `None` when there is none.
+    pub fn load_value<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        let _lock = self.lock()?;
+        self.read_value()
+    }
+
+    /// Changes such a value in one locked read and write, so that other
+    /// instances updating the same file are not overwritten.
+    pub fn update_value<T: DeserializeOwned + Serialize + Default>(&mut self, change: impl FnOnce(&mut T)) -> Result<T> {
+        let _lock = self.lock()?;
+        let mut value = self.read_value()?.unwrap_or_default();
+        change(&mut value);
+        self.write_data(serde_json::to_string_pretty(&value)?)?;
+        Ok(value)
+    }
+
+    /// Writes such a value regardless of what is on disk, e.g. to seal it anew.
+    pub fn replace_value<T: Serialize>(&mut self, value: &T) -> Result<()> {
+        let _lock = self.lock()?;
+        self.write_data(serde_json::to_string_pretty(value)?)
+    }
+
+    fn read_value<T: DeserializeOwned>(&self) -> Result<Option<T>> {
+        let Some(mut raw) = self.read_raw()? else { return Ok(None) };
+        if is_sealed_box(&raw) {
+            raw = self.open(raw)?;
+        }
+        let value = serde_json::from_value(raw).with_context(|| format!("{} is not readable", self.path.display()))?;
+        Ok(Some(value))
+    }
+
+    fn open(&self, raw: Value) -> Result<Value> {
+        let Some(key) = &self.key else { bail!("{} is sealed; unlock it first", self.path.display()) };
+        let sealed: SealedBox = serde_json::from_value(raw).context("the sealed archive is malformed")?;
+        let plaintext = key.open(&sealed)?;
+        serde_json::from_slice(&plaintext).context("the unsealed archive is not readable")
+    }
+
+    fn write(&mut self, notes: &HashMap<String, TimeNote>) -> Result<()> {
+        self.write_data(serde_json::to_string_pretty(&EnvelopeRef { version: SCHEMA_VERSION, notes })?)
+    }
+
+    /// Writes the file through a temporary one so a crash mid-write never
+    /// leaves a truncated archive behind, sealed when a key is set. Callers hold the lock.
+    fn write_data(&mut self, mut data: String) -> Result<()> {
+        if let Some(key) = &self.key {
+            let sealed = key.seal(data.as_bytes())?;
+            data.zeroize();
+            data = serde_json::to_string_pretty(&sealed)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, data).with_context(|| format!("cannot write {}", tmp.display()))?;
+        fs::rename(&tmp, &self.path).with_context(|| format!("cannot replace {}", self.path.display()))?;
+        self.seen = self.fingerprint()?;
+        Ok(())
+    }
+
+    /// True when someone else wrote the archive since this store last read or wrote it.
+    pub fn changed_on_disk(&self) -> Result<bool> {
+        Ok(self.fingerprint()? != self.seen)
+    }
+
+    /// Blocks until this process holds the archive's advisory lock; released on drop.
+    fn lock(&self) -> Result<File> {
+        let lock_path = self.path.with_extension("json.lock");
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("cannot open {}", lock_path.display()))?;
+        file.lock().with_context(|| format!("cannot lock {}", lock_path.display()))?;
+        Ok(file)
+    }
+
+    fn fingerprint(&self) -> Result<Option<Fingerprint>> {
+        let data = match fs::read(&self.path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("cannot read {}", self.path.display())),
+        };
+        let modified = fs::metadata(&self.path).and_then(|m| m.modified()).ok();
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        Ok(Some(Fingerprint { modified, len: data.len() as u64, hash: hasher.finish() }))
+    }
+
+    fn read_raw(&self) -> Result<Option<Value>> {
+        let data = match fs::read_to_string(&self.path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("cannot read {}", self.path.display())),
+        };
+        let raw = serde_json::from_str(&data)
+            .with_context(|| format!("{} is not a readable archive", self.path.display()))?;
+        Ok(Some(raw))
+    }
+
+    /// `chronos_notes.json` -> `chronos_notes.v0.bak.json`, never clobbering an earlier backup.
+    fn backup_path(&self, version: u64) -> PathBuf {
+        let stem = self.stem();
+        let mut backup = self.path.with_file_name(format!("{}.v{}.bak.json", stem, version));
+        let mut n = 1;
+        while backup.exists() {
+            backup = self.path.with_file_name(format!("{}.v{}.bak{}.json", stem, version, n));
+            n += 1;
+        }
+        backup
+    }
+}
+
+fn is_sealed_box(archive: &Value) -> bool {
+    archive.get("cipher").is_some() && archive.get("ciphertext").is_some()
+}
+
+/// Archives before the envelope existed are a bare map of notes: version 0.
+fn schema_version(archive: &Value) -> u64 {
+    match (archive.get("version"), archive.get("notes")) {
+        (Some(Value::Number(v)), Some(Value::Object(_))) => v.as_u64().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+fn migrate_v0_bare_map(archive: Value) -> Result<Value> {
+    if !archive.is_object() {
+        bail!("a v0 archive must be a map of notes");
+    }
+    Ok(json!({ "version": 1, "notes": archive }))
+}
+
+/// v2 lets a note carry passphrase-sealed content. Nothing in a v1 archive
+/// changes; the bump keeps older builds from opening (and dropping) sealed content.
+fn migrate_v1_sealed_nodes(mut archive: Value) -> Result<Value> {
+    archive["version"] = json!(2);
+    Ok(archive)
+}
+
+/// v3 adds per-note modification times and revisions kept by merges. Older
+/// notes simply have neither; the bump protects revisions from older builds.
+fn migrate_v2_revisions(mut archive: Value) -> Result<Value> {
+    archive["version"] = json!(3);
+    Ok(archive)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory for one test's archive.
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("chronos-store-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn migrates(name: &str, archive: Value, version: u64) {
+        let dir = scratch(name);
+        let path = dir.join("chronos_notes.json");
+        fs::write(&path, archive.to_string()).unwrap();
+
+        let notes = Store::new(&path).load().unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes["2024-03-01-09-30"].content, "first light");
+        assert!(notes["2024-03-01-21-05"].is_locked);
+        assert!(notes["2024-03-01-21-05"].modified.is_none());
+
+        let backup = dir.join(format!("chronos_notes.v{}.bak.json", version));
+        let kept: Value = serde_json::from_str(&fs::read_to_string(backup).unwrap()).unwrap();
+        assert_eq!(kept, archive);
+        let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["version"], json!(SCHEMA_VERSION));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    fn bare_map() -> Value {
+        json!({
+            "2024-03-01-09-30": { "content": "first light", "is_locked": false },
+            "2024-03-01-21-05": { "content": "dusk", "is_locked": true },
+        })
+    }
+
+    #[test]
+    fn migrates_a_bare_v0_map() {
+        migrates("v0", bare_map(), 0);
+    }
+
+    #[test]
+    fn migrates_v1_and_v2_envelopes() {
+        migrates("v1", json!({ "version": 1, "notes": bare_map() }), 1);
+        migrates("v2", json!({ "version": 2, "notes": bare_map() }), 2);
+    }
+
+    #[test]
+    fn updates_notes_kept_by_several_instances() {
+        let dir = scratch("update");
+        let path = dir.join("chronos_notes.drafts.json");
+        let (mut one, mut other) = (Store::new(&path), Store::new(&path));
+
+        let note = |content: &str| TimeNote::new(content.to_string());
+        one.update_notes(|d| d.extend([("2024-03-01-09-30".to_string(), note("one"))])).unwrap();
+        other.update_notes(|d| d.extend([("2024-03-01-09-31".to_string(), note("other"))])).unwrap();
+        one.update_notes(|d| d.retain(|key, _| key != "2024-03-01-09-30")).unwrap();
+        assert_eq!(Store::new(&path).load().unwrap().keys().collect::<Vec<_>>(), ["2024-03-01-09-31"]);
+
+        other.update_notes(|d| d.clear()).unwrap();
+        assert!(!path.exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn refuses_a_newer_schema() {
+        let dir = scratch("newer");
+        let path = dir.join("chronos_notes.json");
+        let archive = json!({ "version": SCHEMA_VERSION + 1, "notes": bare_map() }).to_string();
+        fs::write(&path, &archive).unwrap();
+
+        let error = Store::new(&path).load().unwrap_err();
+        assert!(error.to_string().contains("please upgrade"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), archive);
+        assert!(!dir.join(format!("chronos_notes.v{}.bak.json", SCHEMA_VERSION + 1)).exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
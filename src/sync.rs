@@ -0,0 +1,520 @@
+use anyhow::{anyhow, bail, Context, Result};
+use blake2::{Blake2s256, Digest};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::{
+    merge,
+    store::{SaveOutcome, Store},
+    TimeNote,
+};
+
+// --- Confluence over the Wire ---
+// Two archives converge without a cloud: one instrument serves its archive
+// over a small HTTP API, another syncs against it. Each side lists a digest
+// per minute, so only the minutes that differ travel. The sync client keeps
+// the archive as last agreed on as the common ancestor for the next sync.
+//
+//   GET  /manifest   {key: digest} for every note
+//   POST /notes      [key, ...]    -> {key: note} for the requested keys
+//   POST /push       Push          -> PushReport
+
+pub const DEFAULT_ADDR: &str = "127.0.0.1:7341";
+
+const TOKEN_VAR: &str = "CHRONOS_SYNC_TOKEN";
+const MAX_BODY: usize = 64 * 1024 * 1024;
+/// Limits on what a peer may send before its token is even checked.
+const MAX_HEADER_LINE: usize = 8 * 1024;
+const MAX_HEADERS: usize = 64 * 1024;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Changed minutes sent to the server, each with the digest it was based on.
+#[derive(Serialize, Deserialize)]
+struct Push {
+    source: String,
+    /// `None` removes the minute.
+    notes: HashMap<String, Option<TimeNote>>,
+    /// The server's digest the client last saw; `None` when it had no such minute.
+    expected: HashMap<String, Option<String>>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct PushReport {
+    pub applied: usize,
+    /// Minutes changed on the server since the client looked; merged with revisions kept.
+    pub conflicts: Vec<String>,
+}
+
+pub struct SyncReport {
+    pub pulled: usize,
+    pub pushed: usize,
+    pub conflicts: Vec<String>,
+    pub had_ancestor: bool,
+}
+
+/// Fingerprint of everything about a note that sync carries.
+fn digest(note: &TimeNote) -> String {
+    let bytes = serde_json::to_vec(note).unwrap_or_default();
+    Blake2s256::digest(&bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn manifest(notes: &HashMap<String, TimeNote>) -> BTreeMap<String, String> {
+    notes.iter().map(|(key, note)| (key.clone(), digest(note))).collect()
+}
+
+// --- Server ---
+
+pub async fn serve(store: Store, addr: &str) -> Result<()> {
+    let token = std::env::var(TOKEN_VAR).ok().filter(|token| !token.is_empty());
+    // Notes travel as plaintext; without a token, only this machine may read them, and never sealed ones
+    if token.is_none() && store.is_encrypted() {
+        bail!("the archive is sealed, but its notes would be served unencrypted to anyone; set {} first", TOKEN_VAR);
+    }
+    let listener = TcpListener::bind(addr).await.with_context(|| format!("cannot listen on {}", addr))?;
+    let local = listener.local_addr()?;
+    if token.is_none() && !local.ip().is_loopback() {
+        bail!("{} is reachable beyond this machine; set {} to serve there", local, TOKEN_VAR);
+    }
+    println!("Serving the archive on http://{} (Ctrl-C to stop)", local);
+    accept(listener, store, token).await
+}
+
+/// Answers every connection on `listener` until accepting fails.
+async fn accept(listener: TcpListener, store: Store, token: Option<String>) -> Result<()> {
+    let store = Arc::new(Mutex::new(store));
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let store = Arc::clone(&store);
+        let token = token.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, store, token.as_deref()).await {
+                eprintln!("{}: {:#}", peer, e);
+            }
+        });
+    }
+}
+
+async fn handle(mut stream: TcpStream, store: Arc<Mutex<Store>>, token: Option<&str>) -> Result<()> {
+    let request = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut stream)).await {
+        Ok(Ok(request)) => request,
+        Ok(Err(e)) => return respond(&mut stream, 400, &format!("{:#}", e)).await,
+        Err(_) => return respond(&mut stream, 408, "no complete request in time").await,
+    };
+
+    if let Some(token) = token {
+        if !authorized(request.authorization.as_deref(), token) {
+            return respond(&mut stream, 401, "missing or wrong sync token").await;
+        }
+    }
+
+    // Loading and saving block on the archive lock; kept off the async workers
+    let reply = tokio::task::spawn_blocking(move || answer(&store, &request)).await?;
+    match reply {
+        Some(Ok(body)) => respond(&mut stream, 200, &body).await,
+        Some(Err(e)) => respond(&mut stream, 500, &format!("{:#}", e)).await,
+        None => respond(&mut stream, 404, "no such endpoint").await,
+    }
+}
+
+/// Compares the presented token in constant time, so its timing gives nothing away.
+fn authorized(authorization: Option<&str>, token: &str) -> bool {
+    let given = Blake2s256::digest(authorization.unwrap_or_default().as_bytes());
+    let expected = Blake2s256::digest(format!("Bearer {}", token).as_bytes());
+    given.iter().zip(expected.iter()).fold(0, |differ, (a, b)| differ | (a ^ b)) == 0
+}
+
+/// The body of the reply to an endpoint; `None` when there is no such endpoint.
+fn answer(store: &Mutex<Store>, request: &Request) -> Option<Result<String>> {
+    let mut store = match store.lock() {
+        Ok(store) => store,
+        Err(_) => return Some(Err(anyhow!("archive lock poisoned"))),
+    };
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/manifest") => Some(store.load().and_then(|notes| Ok(serde_json::to_string(&manifest(&notes))?))),
+        ("POST", "/notes") => Some(serde_json::from_slice::<Vec<String>>(&request.body)
+            .map_err(Into::into)
+            .and_then(|keys| {
+                let notes = store.load()?;
+                let wanted: HashMap<&String, &TimeNote> = keys.iter().filter_map(|k| Some((k, notes.get(k)?))).collect();
+                Ok(serde_json::to_string(&wanted)?)
+            })),
+        ("POST", "/push") => Some(serde_json::from_slice::<Push>(&request.body)
+            .map_err(Into::into)
+            .and_then(|push| apply_push(&mut store, push))
+            .and_then(|report| Ok(serde_json::to_string(&report)?))),
+        _ => None,
+    }
+}
+
+/// Minutes the server still has as the client saw them are replaced outright;
+/// the rest are merged, keeping the losing version as a revision.
+fn apply_push(store: &mut Store, push: Push) -> Result<PushReport> {
+    // A TUI sharing the archive may save in between; load and try again.
+    for _ in 0..3 {
+        let mut notes = store.load()?;
+        let mut report = PushReport::default();
+        let mut diverged = HashMap::new();
+
+        for (key, incoming) in &push.notes {
+            let current = notes.get(key).map(digest);
+            if current == push.expected.get(key).cloned().flatten() {
+                match incoming {
+                    Some(note) => notes.insert(key.clone(), note.clone()),
+                    None => notes.remove(key),
+                };
+                report.applied += 1;
+            } else if let Some(note) = incoming {
+                diverged.insert(key.clone(), note.clone());
+            }
+        }
+
+        if !diverged.is_empty() {
+            let ours: HashMap<String, TimeNote> =
+                diverged.keys().filter_map(|k| Some((k.clone(), notes.get(k)?.clone()))).collect();
+            let merged = merge::merge(None, &ours, &diverged, &push.source);
+            report.conflicts = diverged.keys().cloned().collect();
+            report.conflicts.sort();
+            notes.extend(merged.notes);
+        }
+
+        if let SaveOutcome::Written = store.save(&notes)? {
+            return Ok(report);
+        }
+    }
+    bail!("the archive kept changing on disk; try again")
+}
+
+// --- Client ---
+
+/// Exchanges every minute that differs with the instrument serving at `url`.
+pub async fn sync(store: &mut Store, url: &str) -> Result<SyncReport> {
+    let remote = Remote::parse(url)?;
+    let ours = store.load()?;
+
+    let their_manifest: BTreeMap<String, String> = remote.call("GET", "/manifest", None).await?;
+    let our_manifest = manifest(&ours);
+    let differing: BTreeSet<String> = our_manifest
+        .keys()
+        .chain(their_manifest.keys())
+        .filter(|key| our_manifest.get(*key) != their_manifest.get(*key))
+        .cloned()
+        .collect();
+
+    let wanted: Vec<&String> = differing.iter().filter(|k| their_manifest.contains_key(*k)).collect();
+    let mut fetched: HashMap<String, TimeNote> = if wanted.is_empty() {
+        HashMap::new()
+    } else {
+        remote.call("POST", "/notes", Some(serde_json::to_vec(&wanted)?)).await?
+    };
+
+    // Their archive as far as it matters: ours where the digests agree, theirs where they do not
+    let mut theirs = ours.clone();
+    for key in &differing {
+        match fetched.remove(key) {
+            Some(note) => theirs.insert(key.clone(), note),
+            None => theirs.remove(key),
+        };
+    }
+
+    let mut snapshot = store.ancestor(&remote.label);
+    let base = if snapshot.path().exists() { Some(snapshot.load()?) } else { None };
+    let merged = merge::merge(base.as_ref(), &ours, &theirs, &remote.label);
+
+    let mut push = Push { source: hostname(), notes: HashMap::new(), expected: HashMap::new() };
+    for key in &differing {
+        let note = merged.notes.get(key);
+        if note.map(digest).as_ref() != their_manifest.get(key) {
+            push.notes.insert(key.clone(), note.cloned());
+            push.expected.insert(key.clone(), their_manifest.get(key).cloned());
+        }
+    }
+
+    if merged.notes != ours {
+        match store.save(&merged.notes)? {
+            SaveOutcome::Written => {}
+            SaveOutcome::ChangedOnDisk => bail!("the archive changed on disk during the sync; nothing was pushed, try again"),
+        }
+    }
+    let report: PushReport = if push.notes.is_empty() {
+        PushReport::default()
+    } else {
+        remote.call("POST", "/push", Some(serde_json::to_vec(&push)?)).await?
+    };
+    snapshot.replace(&merged.notes)?;
+
+    let mut conflicts: Vec<String> = merged.conflicts.into_iter().map(|c| c.key).chain(report.conflicts).collect();
+    conflicts.sort();
+    conflicts.dedup();
+    Ok(SyncReport { pulled: merged.taken, pushed: push.notes.len(), conflicts, had_ancestor: base.is_some() })
+}
+
+/// How revisions left on the server name this machine.
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok().map(|h| h.trim().to_string()))
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "sync".to_string())
+}
+
+struct Remote {
+    /// `host:port`, used to connect and to name the remote in revisions.
+    label: String,
+    prefix: String,
+    token: Option<String>,
+}
+
+impl Remote {
+    fn parse(url: &str) -> Result<Self> {
+        let rest = url.strip_prefix("http://").context("only http:// URLs are supported")?;
+        let (authority, prefix) = rest.split_once('/').map(|(a, p)| (a, format!("/{}", p))).unwrap_or((rest, String::new()));
+        if authority.is_empty() {
+            bail!("no host in {}", url);
+        }
+        let label = if authority.contains(':') { authority.to_string() } else { format!("{}:80", authority) };
+        Ok(Self { label, prefix: prefix.trim_end_matches('/').to_string(), token: std::env::var(TOKEN_VAR).ok() })
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, path: &str, body: Option<Vec<u8>>) -> Result<T> {
+        let mut stream = TcpStream::connect(&self.label).await.with_context(|| format!("cannot reach {}", self.label))?;
+        let body = body.unwrap_or_default();
+
+        let mut head = format!(
+            "{} {}{} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+            method,
+            self.prefix,
+            path,
+            self.label,
+            body.len()
+        );
+        if let Some(token) = &self.token {
+            head.push_str(&format!("Authorization: Bearer {}\r\n", token));
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(&body).await?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await?;
+        let split = response
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .context("malformed response from the server")?;
+        let status_line = String::from_utf8_lossy(&response[..split]).lines().next().unwrap_or_default().to_string();
+        let status: u16 = status_line.split_whitespace().nth(1).and_then(|s| s.parse().ok()).unwrap_or(0);
+        let body = &response[split + 4..];
+
+        if !(200..300).contains(&status) {
+            bail!("{} {} failed: {} {}", method, path, status, String::from_utf8_lossy(body).trim());
+        }
+        serde_json::from_slice(body).with_context(|| format!("unreadable reply to {} {}", method, path))
+    }
+}
+
+// --- Minimal HTTP/1.1 ---
+
+struct Request {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+async fn read_request(stream: &mut TcpStream) -> Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    let mut budget = MAX_HEADERS;
+    read_header_line(&mut reader, &mut line, &mut budget).await?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().context("empty request")?.to_string();
+    let target = parts.next().context("request without a path")?;
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let mut length = 0;
+    let mut authorization = None;
+    loop {
+        line.clear();
+        if read_header_line(&mut reader, &mut line, &mut budget).await? == 0 || line.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else { continue };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => length = value.trim().parse().context("bad Content-Length")?,
+            "authorization" => authorization = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+    if length > MAX_BODY {
+        bail!("request body too large");
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+    Ok(Request { method, path, authorization, body })
+}
+
+/// Reads one line of the request head, refusing overlong lines and heads.
+async fn read_header_line(reader: &mut BufReader<&mut TcpStream>, line: &mut String, budget: &mut usize) -> Result<usize> {
+    let limit = MAX_HEADER_LINE.min(*budget);
+    let read = reader.take(limit as u64).read_line(line).await?;
+    if read == limit && !line.ends_with('\n') {
+        bail!("request header too large");
+    }
+    *budget -= read;
+    Ok(read)
+}
+
+async fn respond(stream: &mut TcpStream, status: u16, body: &str) -> Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        408 => "Request Timeout",
+        _ => "Internal Server Error",
+    };
+    let content_type = if status == 200 { "application/json" } else { "text/plain; charset=utf-8" };
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        reason,
+        content_type,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(body.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, path::PathBuf};
+
+    const SHARED: &str = "2024-03-01-09-30";
+
+    /// An archive in a fresh directory, holding `notes`.
+    fn archive(name: &str, notes: &HashMap<String, TimeNote>) -> Store {
+        let dir = std::env::temp_dir().join(format!("chronos-sync-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let mut store = Store::new(dir.join("chronos_notes.json"));
+        store.replace(notes).unwrap();
+        store
+    }
+
+    fn note(content: &str) -> TimeNote {
+        TimeNote::new(content.to_string())
+    }
+
+    fn load(path: &PathBuf) -> HashMap<String, TimeNote> {
+        Store::new(path).load().unwrap()
+    }
+
+    #[tokio::test]
+    async fn converges_two_archives_on_localhost() {
+        let shared = note("shared");
+        let server = archive("server", &HashMap::from([
+            (SHARED.into(), shared.clone()),
+            ("2024-03-02-08-00".into(), note("theirs")),
+        ]));
+        let server_path = server.path().to_path_buf();
+        let mut client = archive("client", &HashMap::from([
+            (SHARED.into(), shared),
+            ("2024-03-03-08-00".into(), note("ours")),
+        ]));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(accept(listener, server, None));
+
+        // First sync: each side gains the other's minute
+        let report = sync(&mut client, &url).await.unwrap();
+        assert_eq!((report.pulled, report.pushed, report.had_ancestor), (1, 1, false));
+        assert_eq!(load(&server_path).len(), 3);
+        assert_eq!(client.load().unwrap().len(), 3);
+
+        // A deletion travels once the ancestor knows the minute
+        let mut notes = client.load().unwrap();
+        notes.remove("2024-03-02-08-00");
+        client.save(&notes).unwrap();
+        let report = sync(&mut client, &url).await.unwrap();
+        assert_eq!((report.pulled, report.pushed, report.had_ancestor), (0, 1, true));
+        assert!(!load(&server_path).contains_key("2024-03-02-08-00"));
+
+        // Both sides edit the same minute: the newer stays, the other becomes a revision
+        let mut notes = client.load().unwrap();
+        let earlier = chrono::Utc::now() - chrono::Duration::minutes(1);
+        notes.insert(SHARED.into(), TimeNote { modified: Some(earlier), ..note("client edit") });
+        client.save(&notes).unwrap();
+        let mut on_server = Store::new(&server_path);
+        let mut theirs = on_server.load().unwrap();
+        theirs.insert(SHARED.into(), note("server edit"));
+        on_server.save(&theirs).unwrap();
+
+        let report = sync(&mut client, &url).await.unwrap();
+        assert_eq!(report.conflicts, [SHARED]);
+        for notes in [client.load().unwrap(), load(&server_path)] {
+            let kept = &notes[SHARED];
+            assert_eq!(kept.content, "server edit");
+            assert_eq!(kept.revisions.iter().map(|r| r.content.as_str()).collect::<Vec<_>>(), ["client edit"]);
+        }
+
+        for path in [server_path, client.path().to_path_buf()] {
+            fs::remove_dir_all(path.parent().unwrap()).unwrap();
+        }
+    }
+
+    #[test]
+    fn parses_remote_urls() {
+        let remote = Remote::parse("http://192.168.1.20:7341/chronos/").unwrap();
+        assert_eq!((remote.label.as_str(), remote.prefix.as_str()), ("192.168.1.20:7341", "/chronos"));
+        let remote = Remote::parse("http://archive.local").unwrap();
+        assert_eq!((remote.label.as_str(), remote.prefix.as_str()), ("archive.local:80", ""));
+        assert!(Remote::parse("https://archive.local").is_err());
+        assert!(Remote::parse("http:///notes").is_err());
+    }
+
+    #[test]
+    fn authorizes_only_the_exact_token() {
+        assert!(authorized(Some("Bearer s3cret"), "s3cret"));
+        assert!(!authorized(Some("Bearer s3cre"), "s3cret"));
+        assert!(!authorized(Some("Bearer s3cret "), "s3cret"));
+        assert!(!authorized(Some("s3cret"), "s3cret"));
+        assert!(!authorized(None, "s3cret"));
+    }
+
+    #[test]
+    fn merges_pushes_based_on_a_stale_digest() {
+        let current = note("on the server");
+        let mut store = archive("push", &HashMap::from([(SHARED.into(), current.clone())]));
+
+        let push = Push {
+            source: "laptop".into(),
+            notes: HashMap::from([
+                (SHARED.into(), Some(note("from the laptop"))),
+                ("2024-03-04-07-00".into(), Some(note("new"))),
+            ]),
+            expected: HashMap::from([(SHARED.into(), Some("stale".into())), ("2024-03-04-07-00".into(), None)]),
+        };
+        let report = apply_push(&mut store, push).unwrap();
+        assert_eq!((report.applied, report.conflicts.as_slice()), (1, [SHARED.to_string()].as_slice()));
+
+        let notes = store.load().unwrap();
+        assert_eq!(notes["2024-03-04-07-00"].content, "new");
+        let kept = &notes[SHARED];
+        let versions: BTreeSet<&str> =
+            std::iter::once(kept.content.as_str()).chain(kept.revisions.iter().map(|r| r.content.as_str())).collect();
+        assert_eq!(versions, BTreeSet::from(["on the server", "from the laptop"]));
+        fs::remove_dir_all(store.path().parent().unwrap()).unwrap();
+    }
+}
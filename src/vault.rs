@@ -0,0 +1,267 @@
+use chrono::Local;
+use ratatui::{
+    crossterm::event::KeyEvent,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use tui_textarea::TextArea;
+use zeroize::Zeroizing;
+
+use crate::{
+    crypto::{KdfParams, Key},
+    passphrase::{PassphrasePrompt, PromptAction},
+    parse_note_key,
+    pulse::Pulse,
+    App, Revision, TimeNote,
+};
+
+// --- Temporal Observation Vault ---
+// Opening and sealing nodes. A node can be sealed plainly (`is_locked`) or,
+// beyond that, encrypted under its own passphrase, separate from the archive's.
+
+pub enum PromptPurpose {
+    /// Opening a passphrase-sealed node in the vault.
+    OpenSealed(String),
+    /// Choosing, then repeating, a passphrase for a node.
+    SealWith { key: String, first: Option<Zeroizing<String>> },
+    /// Lifting the passphrase seal from a node for good.
+    Unseal(String),
+}
+
+pub struct ActivePrompt {
+    pub prompt: PassphrasePrompt,
+    purpose: PromptPurpose,
+}
+
+impl ActivePrompt {
+    fn new(title: &str, purpose: PromptPurpose) -> Self {
+        Self { prompt: PassphrasePrompt::new(title), purpose }
+    }
+}
+
+impl App<'_> {
+    /// Enter: opens the selected node, asking for its passphrase when it has one.
+    pub fn open_vault(&mut self) {
+        if self.selected_minute.is_none() {
+            return;
+        }
+        let Some(key) = self.selected_key() else {
+            self.status = Some("THIS RING GATHERS MANY DAYS: PRESS g FOR DAILY RINGS TO OPEN ITS NODES".to_string());
+            return;
+        };
+        self.open_node(key);
+    }
+
+    /// Opens the node banked under `key`, wherever it lies.
+    pub fn open_node(&mut self, key: String) {
+        match self.notes.get(&key) {
+            Some(note) if note.is_encrypted() => {
+                self.prompt = Some(ActivePrompt::new("SEALED NODE: SPEAK ITS PASSPHRASE", PromptPurpose::OpenSealed(key)));
+            }
+            Some(note) => {
+                let content = note.content.clone();
+                self.begin_editing(key, &content, None);
+            }
+            None => self.begin_editing(key, "", None),
+        }
+    }
+
+    fn begin_editing(&mut self, key: String, content: &str, vault_key: Option<Key>) {
+        self.textarea = TextArea::from(content.lines());
+        let minute = parse_note_key(&key).map(|(_, _, m)| m).unwrap_or(0);
+        let title = format!("Temporal Observation Node: Minute {:02}", minute);
+        self.textarea.set_block(
+            Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Style::default().fg(Color::Rgb(212, 175, 55))),
+        );
+        self.editing_key = Some(key);
+        self.last_draft = Some(content.to_string());
+        self.vault_key = vault_key;
+        self.is_editing = true;
+    }
+
+    /// Esc: banks the vault contents, keeping the node's seal (and passphrase) intact.
+    pub fn close_vault(&mut self) {
+        self.is_editing = false;
+        self.last_draft = None;
+        let Some(key) = self.editing_key.take() else { return };
+        let content = self.textarea.lines().join("\n");
+
+        let mut note = match self.notes.get(&key) {
+            Some(previous) => TimeNote { content, sealed: None, ..previous.clone() },
+            None => TimeNote::new(content),
+        };
+        note.touch();
+        if let Some(vault_key) = self.vault_key.take() {
+            match vault_key.seal(note.content.as_bytes()) {
+                Ok(sealed) => {
+                    note.content.clear();
+                    note.sealed = Some(sealed);
+                }
+                Err(e) => {
+                    self.status = Some(format!("NODE NOT RESEALED: {:#}", e));
+                    return;
+                }
+            }
+        }
+        self.settled_drafts.push(key.clone());
+        self.notes.insert(key, note);
+        self.persist();
+    }
+
+    /// s: seals or unseals the selected node; a passphrase seal asks for its passphrase first.
+    pub fn toggle_seal(&mut self) {
+        let Some(key) = self.selected_key() else { return };
+        let Some(note) = self.notes.get_mut(&key) else { return };
+
+        if note.is_encrypted() {
+            self.prompt = Some(ActivePrompt::new("UNSEAL NODE: SPEAK ITS PASSPHRASE", PromptPurpose::Unseal(key)));
+        } else {
+            note.is_locked = !note.is_locked;
+            note.touch();
+            self.persist();
+        }
+    }
+
+    /// S: seals the selected node under a passphrase of its own.
+    pub fn seal_with_passphrase(&mut self) {
+        let Some(key) = self.selected_key() else { return };
+        match self.notes.get(&key) {
+            Some(note) if !note.is_encrypted() => {
+                self.prompt = Some(ActivePrompt::new(
+                    "SEAL NODE: CHOOSE A PASSPHRASE",
+                    PromptPurpose::SealWith { key, first: None },
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_prompt_key(&mut self, key: KeyEvent) {
+        let Some(active) = self.prompt.as_mut() else { return };
+        if active.prompt.busy {
+            return;
+        }
+        match active.prompt.handle_key(key) {
+            PromptAction::Pending => {}
+            PromptAction::Cancel => self.prompt = None,
+            PromptAction::Submit(secret) => {
+                let kdf = match &active.purpose {
+                    PromptPurpose::SealWith { key, first: None } => {
+                        // No derivation yet; ask for the passphrase a second time.
+                        let purpose = PromptPurpose::SealWith { key: key.clone(), first: Some(secret) };
+                        *active = ActivePrompt::new("SEAL NODE: REPEAT THE PASSPHRASE", purpose);
+                        return;
+                    }
+                    PromptPurpose::SealWith { key, first: Some(first) } => {
+                        if **first != *secret {
+                            let purpose = PromptPurpose::SealWith { key: key.clone(), first: None };
+                            *active = ActivePrompt::new("SEAL NODE: CHOOSE A PASSPHRASE", purpose);
+                            active.prompt.error = Some("the passphrases differ; choose again".to_string());
+                            return;
+                        }
+                        KdfParams::generate()
+                    }
+                    PromptPurpose::OpenSealed(key) | PromptPurpose::Unseal(key) => {
+                        match self.notes.get(key).and_then(|n| n.sealed.as_ref()) {
+                            Some(sealed) => sealed.kdf.clone(),
+                            None => {
+                                self.prompt = None;
+                                return;
+                            }
+                        }
+                    }
+                };
+
+                // Key stretching takes a while; the breath goes on meanwhile.
+                active.prompt.busy = true;
+                let pulses = self.pulses.clone();
+                tokio::task::spawn_blocking(move || {
+                    let _ = pulses.send(Pulse::KeyDerived(Key::derive(&secret, &kdf)));
+                });
+            }
+        }
+    }
+
+    /// Uses the key a submitted prompt asked for, once it has been derived.
+    pub fn resolve_prompt(&mut self, derived: anyhow::Result<Key>) {
+        let Some(mut active) = self.prompt.take() else { return };
+        active.prompt.busy = false;
+
+        let outcome = derived.and_then(|node_key| match &active.purpose {
+            PromptPurpose::OpenSealed(key) => self.open_sealed(key, node_key),
+            PromptPurpose::Unseal(key) => self.unseal(key, &node_key),
+            PromptPurpose::SealWith { key, .. } => self.seal(key, &node_key),
+        });
+
+        if let Err(e) = outcome {
+            active.prompt.error = Some(format!("{:#}", e));
+            self.prompt = Some(active);
+        }
+    }
+
+    fn open_sealed(&mut self, key: &str, node_key: Key) -> anyhow::Result<()> {
+        let Some(sealed) = self.notes.get(key).and_then(|n| n.sealed.as_ref()) else { return Ok(()) };
+        let plaintext = node_key.open(sealed)?;
+        let content = String::from_utf8_lossy(&plaintext).into_owned();
+        self.begin_editing(key.to_string(), &content, Some(node_key));
+        Ok(())
+    }
+
+    fn unseal(&mut self, key: &str, node_key: &Key) -> anyhow::Result<()> {
+        let Some(note) = self.notes.get_mut(key) else { return Ok(()) };
+        let Some(sealed) = note.sealed.as_ref() else { return Ok(()) };
+        let plaintext = node_key.open(sealed)?;
+        note.content = String::from_utf8_lossy(&plaintext).into_owned();
+        note.sealed = None;
+        note.is_locked = false;
+        note.touch();
+        self.persist();
+        Ok(())
+    }
+
+    fn seal(&mut self, key: &str, node_key: &Key) -> anyhow::Result<()> {
+        let Some(note) = self.notes.get_mut(key) else { return Ok(()) };
+        note.sealed = Some(node_key.seal(note.content.as_bytes())?);
+        note.content.clear();
+        note.is_locked = true;
+        note.touch();
+        self.persist();
+        Ok(())
+    }
+}
+
+/// Read-only list of the versions a merge set aside for the open node.
+pub fn draw_revisions(f: &mut Frame, area: Rect, revisions: &[Revision]) {
+    let gold = Color::Rgb(212, 175, 55);
+    f.render_widget(Clear, area);
+
+    let mut lines = Vec::new();
+    for revision in revisions.iter().rev() {
+        let when = revision
+            .modified
+            .map(|m| m.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "undated".to_string());
+        lines.push(Line::from(Span::styled(
+            format!(" {} · from {}", when, revision.source),
+            Style::default().fg(gold).add_modifier(Modifier::BOLD),
+        )));
+        let body = if revision.sealed.is_some() {
+            "(sealed under its own passphrase)".to_string()
+        } else {
+            revision.content.lines().collect::<Vec<_>>().join(" / ")
+        };
+        lines.push(Line::from(Span::styled(format!("   {}", body), Style::default().fg(Color::Rgb(252, 246, 186)))));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(Style::default().fg(Color::DarkGray))
+        .title(Span::styled(format!(" SET-ASIDE REVISIONS ({}) ", revisions.len()), Style::default().fg(gold)));
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
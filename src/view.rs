@@ -0,0 +1,97 @@
+use crate::{rings, App};
+
+// --- Zoom and Pan ---
+// The canvas looks at the astrolabe through a square window that can be
+// moved and scaled. A year of rings does not fit the default window, so
+// the view can be fitted to the outermost ring and follows the cursor
+// whenever it moves out of sight.
+
+/// Each zoom step scales the window by this much.
+pub const ZOOM_STEP: f64 = 1.25;
+/// Each pan step moves the window by this share of its width.
+const PAN_STEP: f64 = 0.2;
+/// Closest zoom: the inner watch and its petals fill the canvas.
+const MIN_EXTENT: f64 = 40.0;
+/// Room left around the outermost ring (and around the cursor when following it).
+const MARGIN: f64 = 15.0;
+
+pub struct Viewport {
+    pub x: f64,
+    pub y: f64,
+    /// Half the width (and height) of the window in astrolabe units.
+    pub half_extent: f64,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Self { x: 0.0, y: 0.0, half_extent: rings::CANVAS_BOUND }
+    }
+}
+
+impl Viewport {
+    pub fn x_bounds(&self) -> [f64; 2] {
+        [self.x - self.half_extent, self.x + self.half_extent]
+    }
+
+    pub fn y_bounds(&self) -> [f64; 2] {
+        [self.y - self.half_extent, self.y + self.half_extent]
+    }
+
+    /// How much larger things look than in the default window.
+    pub fn zoom(&self) -> f64 {
+        rings::CANVAS_BOUND / self.half_extent
+    }
+}
+
+impl App<'_> {
+    /// Scales the window about its centre; `factor` above one zooms in.
+    pub fn zoom(&mut self, factor: f64) {
+        let widest = self.fitted_extent().max(rings::CANVAS_BOUND) * 2.0;
+        self.view.half_extent = (self.view.half_extent / factor).clamp(MIN_EXTENT, widest);
+        self.keep_cursor_in_view();
+    }
+
+    /// Moves the window by whole steps; positive `dx` goes east, positive `dy` north.
+    pub fn pan(&mut self, dx: f64, dy: f64) {
+        let step = 2.0 * self.view.half_extent * PAN_STEP;
+        self.view.x += dx * step;
+        self.view.y += dy * step;
+    }
+
+    /// Centres the window and scales it to the outermost ring.
+    pub fn fit_rings(&mut self) {
+        self.view = Viewport { half_extent: self.fitted_extent(), ..Viewport::default() };
+    }
+
+    /// Fits the rings when they outgrew the default window, else returns to it.
+    pub fn refit(&mut self) {
+        if self.fitted_extent() > rings::CANVAS_BOUND {
+            self.fit_rings();
+        } else {
+            self.view = Viewport::default();
+        }
+    }
+
+    /// Runs `change` and, if it moved the cursor, brings the cursor into sight.
+    pub fn following_cursor(&mut self, change: impl FnOnce(&mut Self)) {
+        let cursor = (self.selected_ring_index, self.selected_minute, self.pinned_hour);
+        change(self);
+        if (self.selected_ring_index, self.selected_minute, self.pinned_hour) != cursor {
+            self.keep_cursor_in_view();
+        }
+    }
+
+    /// Slides the window just far enough to bring the cursor back into sight.
+    pub fn keep_cursor_in_view(&mut self) {
+        let Some((x, y)) = self.cursor_point() else { return };
+        let reach = (self.view.half_extent - MARGIN).max(0.0);
+        self.view.x = self.view.x.clamp(x - reach, x + reach);
+        self.view.y = self.view.y.clamp(y - reach, y + reach);
+    }
+
+    fn fitted_extent(&self) -> f64 {
+        let outermost = self.rings.last().map_or(0.0, |ring| ring.radius);
+        // The lotus petals reach past the inner watch
+        outermost.max(rings::PETAL_RADIUS) + MARGIN
+    }
+}